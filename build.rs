@@ -0,0 +1,242 @@
+//! Generates the solution registry of the main binary, the example tests of each solution,
+//! and the embedded inputs if the `embed_inputs` feature is enabled.
+//! Every `NN.rs` and `YYYY-NN.rs` in the `bin_dir` of `aoc.toml` is included as a module,
+//! so `all` and `verify` can run all days of a year in-process.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+    let bin_dir = manifest_dir.join(get_setting(
+        manifest_dir,
+        "bin_dir",
+        "AOC_BIN_DIR",
+        "src/bin",
+    ));
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut bins: Vec<(Option<String>, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    parse_bin_name(path.file_stem()?.to_str()?)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort();
+
+    let mut code = String::new();
+    let mut candidates: Vec<String> = vec![];
+
+    for (year, day) in &bins {
+        let (name, module, year) = match year {
+            Some(year) => (
+                format!("{year}-{day}"),
+                format!("day_{year}_{day}"),
+                format!("Some({year})"),
+            ),
+            None => (day.clone(), format!("day_{day}"), "None".to_string()),
+        };
+        let path = bin_dir.join(format!("{name}.rs"));
+        code.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {:?}]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        candidates.push(format!("({year}, {module}::__register)"));
+    }
+
+    code.push_str(&format!(
+        "\n#[cfg(not(test))]\nconst CANDIDATES: &[advent_of_code::template::registry::Candidate] = &[{}];\n",
+        candidates.join(", ")
+    ));
+    code.push_str(
+        "\n#[cfg(test)]\nconst CANDIDATES: &[advent_of_code::template::registry::Candidate] = &[];\n",
+    );
+    code.push_str(
+        "\npub fn solutions(year: advent_of_code::Year) -> Vec<advent_of_code::template::registry::Entry> {\n    advent_of_code::template::registry::select(year, CANDIDATES)\n}\n",
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), code).unwrap();
+
+    // lets `solution!` check the day of `NN` binaries against the calendar of the main year.
+    if let Some(year) = get_main_year(manifest_dir) {
+        println!("cargo:rustc-env=AOC_MAIN_YEAR={year}");
+    }
+
+    let data_dir = manifest_dir.join(get_setting(
+        manifest_dir,
+        "data_dir",
+        "AOC_DATA_DIR",
+        "data",
+    ));
+
+    let examples_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+    for (year, day) in &bins {
+        let (name, dir) = match year {
+            Some(year) => (
+                format!("{year}-{day}"),
+                data_dir.join(year).join("examples"),
+            ),
+            None => (day.clone(), data_dir.join("examples")),
+        };
+        if dir.exists() {
+            println!("cargo:rerun-if-changed={}", dir.display());
+        }
+        fs::write(
+            examples_dir.join(format!("{name}.rs")),
+            example_tests(year.as_deref(), &dir.join(format!("{day}.json"))),
+        )
+        .unwrap();
+    }
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        fs::write(
+            Path::new(&out_dir).join("inputs.rs"),
+            embed_inputs(&data_dir),
+        )
+        .unwrap();
+    }
+}
+
+/// Generates a test for each example and part with an expected answer in the manifest at `path`.
+/// The tests are included into the solution by `example_tests!`.
+fn example_tests(year: Option<&str>, path: &Path) -> String {
+    let Ok(manifest) = fs::read_to_string(path) else {
+        return String::new();
+    };
+    let manifest: serde_json::Value = serde_json::from_str(&manifest)
+        .unwrap_or_else(|e| panic!("could not parse {}: {e}", path.display()));
+
+    let year = year.map_or("None".into(), |year| {
+        format!("Some(advent_of_code::year!({year}))")
+    });
+
+    let mut code = String::new();
+    let examples = manifest["examples"].as_array().cloned().unwrap_or_default();
+    for example in &examples {
+        let Some(name) = example["name"].as_str() else {
+            continue;
+        };
+        for part in 1..=2 {
+            let Some(expected) = example[format!("part_{part}")].as_str() else {
+                continue;
+            };
+            let test_name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            code.push_str(&format!(
+                "#[test]\nfn example_{test_name}_part_{part}() {{\n    advent_of_code::template::examples::check(&super::__register(), {year}, {name:?}, {part}, {expected:?});\n}}\n\n"
+            ));
+        }
+    }
+    code
+}
+
+/// Includes every non-empty input of `data_dir/inputs` (the main year) and `data_dir/YYYY/inputs`.
+fn embed_inputs(data_dir: &Path) -> String {
+    println!("cargo:rerun-if-changed={}", data_dir.display());
+
+    let mut dirs = vec![(None, data_dir.join("inputs"))];
+    if let Ok(entries) = fs::read_dir(data_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.len() == 4 && name.chars().all(|c| c.is_ascii_digit()) {
+                dirs.push((Some(name), entry.path().join("inputs")));
+            }
+        }
+    }
+
+    let mut inputs: Vec<String> = vec![];
+    for (year, dir) in dirs {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let day = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let is_input = path.extension().is_some_and(|x| x == "txt")
+                && day.len() == 2
+                && day.chars().all(|c| c.is_ascii_digit());
+            if !is_input || fs::metadata(&path).map_or(true, |m| m.len() == 0) {
+                continue;
+            }
+            let year = year
+                .as_ref()
+                .map_or("None".into(), |year| format!("Some({year})"));
+            inputs.push(format!(
+                "({year}, {}, include_str!({:?}))",
+                day.trim_start_matches('0'),
+                path.display().to_string()
+            ));
+        }
+    }
+    inputs.sort();
+
+    format!(
+        "/// Puzzle inputs by year (`None` for the main year) and day.\npub const INPUTS: &[(Option<u16>, u8, &str)] = &[{}];\n",
+        inputs.join(", ")
+    )
+}
+
+/// Reads a path from its environment variable or the config file, the library parses the full config.
+fn get_setting(manifest_dir: &Path, key: &str, var: &str, default: &str) -> PathBuf {
+    println!("cargo:rerun-if-env-changed={var}");
+    println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+
+    if let Some(value) = env::var_os(var) {
+        return value.into();
+    }
+
+    let config = env::var_os("AOC_CONFIG").map_or_else(|| "aoc.toml".into(), PathBuf::from);
+    let config = manifest_dir.join(config);
+    println!("cargo:rerun-if-changed={}", config.display());
+
+    fs::read_to_string(&config)
+        .ok()
+        .and_then(|s| s.parse::<toml::Table>().ok())
+        .and_then(|table| table.get(key)?.as_str().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+/// Reads the main year from `AOC_YEAR` or the config file.
+fn get_main_year(manifest_dir: &Path) -> Option<u16> {
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    if let Some(year) = env::var_os("AOC_YEAR") {
+        return year.to_str()?.parse().ok();
+    }
+
+    let config = env::var_os("AOC_CONFIG").map_or_else(|| "aoc.toml".into(), PathBuf::from);
+    fs::read_to_string(manifest_dir.join(config))
+        .ok()
+        .and_then(|s| s.parse::<toml::Table>().ok())
+        .and_then(|table| u16::try_from(table.get("year")?.as_integer()?).ok())
+}
+
+/// Splits a binary name into its year and day: `NN` for the main year, `YYYY-NN` for other years.
+fn parse_bin_name(name: &str) -> Option<(Option<String>, String)> {
+    let is_number = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
+    match name.split_once('-') {
+        Some((year, day)) => {
+            (is_number(year, 4) && is_number(day, 2)).then(|| (Some(year.into()), day.into()))
+        }
+        None => is_number(name, 2).then(|| (None, name.into())),
+    }
+}
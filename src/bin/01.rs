@@ -6,9 +6,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     let result = input
         .lines()
         .map(|line| line.chars().filter(|&c| c.is_numeric()).collect::<String>())
-        .filter(|line| line.len() >= 1)
+        .filter(|line| !line.is_empty())
         .map(|line| {
-            line.chars().nth(0).unwrap().to_digit(10).unwrap() * 10
+            line.chars().next().unwrap().to_digit(10).unwrap() * 10
                 + line.chars().last().unwrap().to_digit(10).unwrap()
         })
         .sum();
@@ -36,19 +36,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     part_one(&res)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_filepath("examples", "01-b"));
-        assert_eq!(result, Some(281));
-    }
-}
+advent_of_code::example_tests!();
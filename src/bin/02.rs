@@ -1,18 +1,21 @@
-use lazy_static;
 use regex::Regex;
 use std::cmp::max;
 use std::str::FromStr;
 
 advent_of_code::solution!(2);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = input.lines().map(|line| Game::from_str(line).unwrap());
+pub fn part_one(input: &str) -> Result<u32, &'static str> {
+    let games = input
+        .lines()
+        .map(Game::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
 
     // The Elf would first like to know which games would have been possible if the bag contained only
     // 12 red cubes, 13 green cubes, and 14 blue cubes
     let max: (u32, u32, u32) = (12, 13, 14);
 
-    games
+    Ok(games
+        .into_iter()
         .filter_map(|game| {
             for round in game.rounds {
                 if round.red > max.0 || round.green > max.1 || round.blue > max.2 {
@@ -21,14 +24,17 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
             Some(game.id)
         })
-        .sum::<u32>()
-        .into()
+        .sum::<u32>())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = input.lines().map(|line| Game::from_str(line).unwrap());
+pub fn part_two(input: &str) -> Result<u32, &'static str> {
+    let games = input
+        .lines()
+        .map(Game::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
 
-    games
+    Ok(games
+        .into_iter()
         .filter_map(|game| {
             game.rounds
                 .into_iter()
@@ -37,10 +43,9 @@ pub fn part_two(input: &str) -> Option<u32> {
                     green: max(a.green, b.green),
                     blue: max(a.blue, b.blue),
                 })
-                .and_then(|round| Some(round.red * round.green * round.blue))
+                .map(|round| round.red * round.green * round.blue)
         })
-        .sum::<u32>()
-        .into()
+        .sum::<u32>())
 }
 
 #[derive(Debug)]
@@ -67,7 +72,7 @@ impl FromStr for Game {
             .1
             .parse::<u32>()
             .map_err(|_| -> &str { "Invalid game format" })?;
-        let rounds = r.split("; ").map(|round| Round::new(round)).collect();
+        let rounds = r.split("; ").map(Round::new).collect();
         Ok(Self { id, rounds })
     }
 }
@@ -94,22 +99,12 @@ impl Round {
     }
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
-    }
-
     #[test]
     fn test_parser() {
         let input = "Game 1: 3 blue, 4 red; 5 green, 2 blue";
@@ -5,8 +5,8 @@ advent_of_code::solution!(3);
 pub fn part_one(input: &str) -> Option<u32> {
     let (partlist, linklist) = parse_input(input);
     let res = partlist
-        .iter()
-        .filter_map(|(_pos, part)| {
+        .values()
+        .filter_map(|part| {
             if part
                 .neighbours()
                 .iter()
@@ -58,7 +58,7 @@ impl Part {
     fn neighbours(&self) -> Vec<(i32, i32)> {
         let mut neighbours = Vec::new();
         for x in (self.x - 1)..=self.x + self.width {
-            for y in vec![self.y - 1, self.y + 1] {
+            for y in [self.y - 1, self.y + 1] {
                 neighbours.push((x, y));
             }
         }
@@ -68,9 +68,12 @@ impl Part {
     }
 }
 
-fn parse_input(input: &str) -> (HashMap<(i32, i32), Part>, HashMap<(i32, i32), Link>) {
-    let mut partlist: HashMap<(i32, i32), Part> = HashMap::new();
-    let mut linklist: HashMap<(i32, i32), Link> = HashMap::new();
+type PartList = HashMap<(i32, i32), Part>;
+type LinkList = HashMap<(i32, i32), Link>;
+
+fn parse_input(input: &str) -> (PartList, LinkList) {
+    let mut partlist: PartList = HashMap::new();
+    let mut linklist: LinkList = HashMap::new();
     input.lines().enumerate().for_each(|(y, line)| {
         let mut active_part = Option::<Part>::None;
         line.chars().enumerate().for_each(|(x, c)| {
@@ -114,22 +117,12 @@ fn parse_input(input: &str) -> (HashMap<(i32, i32), Part>, HashMap<(i32, i32), L
     (partlist, linklist)
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
-    }
-
     #[test]
     fn test_neighbours() {
         let part = Part {
@@ -1,56 +1,70 @@
 #![allow(dead_code)]
-use regex::Regex;
+use advent_of_code::Solution;
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
-
-pub fn part_one(input: &str) -> Option<u64> {
-    let (seeds, maps) = input.split_once("\n\n").unwrap();
-    let seeds = seeds
-        .split(": ")
-        .last()
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>();
-    let maps: Vec<Mapper> = maps.split("\n\n").filter_map(|m| m.parse().ok()).collect();
-
-    seeds
-        .iter()
-        .map(|s| {
-            let mut value = *s;
-            for map in maps.iter() {
-                value = map.map(value);
-            }
-            value
-        })
-        .min()
-}
+advent_of_code::solution!(5 => Day05);
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Output1 = Option<u64>;
+    type Output2 = Option<u64>;
+    type Error = String;
+
+    fn parse(input: &str) -> Result<Almanac, String> {
+        let (seeds, maps) = input
+            .split_once("\n\n")
+            .ok_or("missing maps after the seeds")?;
+        let seeds = seeds
+            .split(": ")
+            .last()
+            .unwrap()
+            .split_whitespace()
+            .map(|s| s.parse::<u64>().map_err(|e| e.to_string()))
+            .collect::<Result<Vec<u64>, String>>()?;
+        let maps = maps.split("\n\n").filter_map(|m| m.parse().ok()).collect();
+
+        Ok(Almanac { seeds, maps })
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let re = Regex::new(r"(\d+) (\d+)").unwrap();
-
-    let (seeds, maps) = input.split_once("\n\n").unwrap();
-    let mut seeds = re
-        .captures_iter(seeds)
-        .map(|c| (c[1].parse::<u64>().unwrap(), c[2].parse::<u64>().unwrap()))
-        .collect::<Vec<(u64, u64)>>();
-    let maps: Vec<Mapper> = maps.split("\n\n").filter_map(|m| m.parse().ok()).collect();
-
-    let res = maps
-        .iter()
-        .map(|m| {
-            let mut next: Vec<(u64, u64)> = Vec::new();
-            for seed in seeds.iter() {
-                let mapped = m.map_range(*seed);
-                next.extend(mapped);
-            }
-            seeds = next;
-            seeds.clone()
-        })
-        .last()
-        .unwrap();
-    res.iter().map(|(a, _)| a).min().copied()
+    fn part_one(almanac: &Almanac) -> Option<u64> {
+        almanac
+            .seeds
+            .iter()
+            .map(|s| {
+                let mut value = *s;
+                for map in almanac.maps.iter() {
+                    value = map.map(value);
+                }
+                value
+            })
+            .min()
+    }
+
+    fn part_two(almanac: &Almanac) -> Option<u64> {
+        let mut seeds = almanac
+            .seeds
+            .chunks_exact(2)
+            .map(|c| (c[0], c[1]))
+            .collect::<Vec<(u64, u64)>>();
+
+        let res = almanac
+            .maps
+            .iter()
+            .map(|m| {
+                let mut next: Vec<(u64, u64)> = Vec::new();
+                for seed in seeds.iter() {
+                    let mapped = m.map_range(*seed);
+                    next.extend(mapped);
+                }
+                seeds = next;
+                seeds.clone()
+            })
+            .last()
+            .unwrap();
+        res.iter().map(|(a, _)| a).min().copied()
+    }
 }
 
 impl FromStr for Mapper {
@@ -123,6 +137,12 @@ impl Mapper {
     }
 }
 
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Mapper>,
+}
+
 #[derive(Debug)]
 struct Mapper {
     header: String,
@@ -131,22 +151,12 @@ struct Mapper {
 
 type Map = (u64, u64, u64); // Dest range, Src range, range length
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
-    }
-
     #[test]
     fn test_mapping() {
         let mapper = Mapper {
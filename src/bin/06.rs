@@ -64,22 +64,12 @@ impl Race {
     }
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
     #[test]
     fn test_parse() {
         let input = "Time:      7  15   30
@@ -54,7 +54,7 @@ enum Rank {
     FiveOfAKind,
 }
 
-#[derive(Debug, Eq, Ord)]
+#[derive(Debug, Eq)]
 struct Hand {
     cards: Vec<Card>,
     rank: Rank,
@@ -64,12 +64,7 @@ struct Hand {
 impl Hand {
     fn new(input: &str) -> Hand {
         let (cards, bet) = input.split_once(' ').unwrap();
-        let cards = cards
-            .chars()
-            .map(Card::new)
-            .filter(Result::is_ok)
-            .map(Result::unwrap)
-            .collect();
+        let cards = cards.chars().flat_map(Card::new).collect();
         Hand {
             rank: determine_rank(&cards),
             cards,
@@ -84,8 +79,8 @@ impl PartialEq for Hand {
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match self.rank.cmp(&other.rank) {
             std::cmp::Ordering::Equal => {
                 let compares = self
@@ -96,16 +91,22 @@ impl PartialOrd for Hand {
                 for c in compares {
                     match c.0.cmp(c.1) {
                         std::cmp::Ordering::Equal => continue,
-                        other => return Some(other),
+                        other => return other,
                     }
                 }
-                Some(std::cmp::Ordering::Equal)
+                std::cmp::Ordering::Equal
             }
-            other => Some(other),
+            other => other,
         }
     }
 }
 
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 fn determine_rank(cards: &Vec<Card>) -> Rank {
     let mut uniques = HashMap::new();
     for card in cards {
@@ -162,22 +163,12 @@ impl Card {
     }
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
-    }
-
     #[test]
     fn test_ord() {
         assert!(Card::Two < Card::Three);
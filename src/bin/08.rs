@@ -35,7 +35,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let start_nodes = nodes
         .iter()
         .filter(|(k, _)| k.ends_with("A"))
-        .map(|(_, v)| (v))
+        .map(|(_, v)| v)
         .collect::<Vec<&Node>>();
 
     let steps = start_nodes
@@ -107,19 +107,4 @@ enum Direction {
     Right,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_filepath("examples", "08-b"));
-        assert_eq!(result, Some(6));
-    }
-}
+advent_of_code::example_tests!();
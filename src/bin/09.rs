@@ -1,22 +1,29 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, parse);
 
-pub fn part_one(input: &str) -> Option<i128> {
-    let s = input.lines().map(|l| l.into()).collect::<Vec<Series>>();
-    s.iter()
+pub fn parse(input: &str) -> Vec<Series> {
+    input.lines().map(|l| l.into()).collect()
+}
+
+pub fn part_one(input: &[Series]) -> Option<i128> {
+    input
+        .iter()
         .map(|s| s.forecast(s.numbers.len() as i128))
         .sum::<i128>()
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<i128> {
-    let s = input.lines().map(|l| l.into()).collect::<Vec<Series>>();
-    s.iter().map(|s| s.forecast(-1_i128)).sum::<i128>().into()
+pub fn part_two(input: &[Series]) -> Option<i128> {
+    input
+        .iter()
+        .map(|s| s.forecast(-1_i128))
+        .sum::<i128>()
+        .into()
 }
 
 #[derive(Debug)]
-struct Series {
+pub struct Series {
     numbers: Vec<i128>,
     factors: Vec<i128>,
 }
@@ -25,7 +32,7 @@ impl From<&str> for Series {
     fn from(input: &str) -> Self {
         let numbers = input
             .split(' ')
-            .map(|line| line.parse::<i128>().unwrap() as i128)
+            .map(|line| line.parse::<i128>().unwrap())
             .collect::<Vec<i128>>();
         let mut next = numbers.clone();
         let mut factors = Vec::new();
@@ -49,7 +56,7 @@ impl Series {
         self.factors
             .iter()
             .enumerate()
-            .map(|(i, f)| (f * x.fact_partial(i as i128) / (i as i128).fact()))
+            .map(|(i, f)| f * x.fact_partial(i as i128) / (i as i128).fact())
             .sum()
     }
 }
@@ -72,22 +79,12 @@ impl Fact for i128 {
     }
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
     #[test]
     fn test_factorials() {
         assert_eq!(0.fact(), 1);
@@ -1,223 +1,238 @@
+use advent_of_code::Solution;
 use std::str::FromStr;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10 => Day10);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut m = input.parse::<Maze>().unwrap();
-    while m.step() {}
-    Some(m.steps / 2)
-}
+pub struct Day10;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut m = input.parse::<Maze>().unwrap();
-    let mut classified_map = vec![vec![Classification::Unvisited; m.pipes[0].len()]; m.pipes.len()];
-    classified_map[m.current.1][m.current.0] = Classification::Pipe;
+impl Solution for Day10 {
+    type Input = Maze;
+    type Output1 = Option<u32>;
+    type Output2 = Option<u32>;
+    type Error = String;
 
-    // 1 round to classify all pipes
-    while m.step() {
-        classified_map[m.current.1][m.current.0] = Classification::Pipe;
+    fn parse(input: &str) -> Result<Maze, String> {
+        input.parse()
     }
 
-    // 2nd round to find the areas to the left and the right of the walking path
-    m.direction = Direction::West;
-    while m.step() {
-        let (x, y) = (m.current.0, m.current.1);
-        match (&m.direction, &m.pipes[m.current.1][m.current.0]) {
-            // Straight pipes
-            (Direction::North, Pipe::NS) => {
-                if x > 0 && (classified_map[y][x - 1] == Classification::Unvisited) {
-                    classified_map[y][x - 1] = Classification::Left;
-                }
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Right;
-                }
-            }
-            (Direction::South, Pipe::NS) => {
-                if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
-                    classified_map[y][x - 1] = Classification::Right;
-                }
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Left;
-                }
-            }
-            (Direction::East, Pipe::EW) => {
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Left;
-                }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Right;
-                }
-            }
-            (Direction::West, Pipe::EW) => {
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Right;
-                }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Left;
-                }
-            }
-            // Corner pipes
-            (Direction::North, Pipe::NE) => {
-                if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
-                    classified_map[y][x - 1] = Classification::Left;
-                }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Left;
-                }
-            }
-            (Direction::East, Pipe::NE) => {
-                if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
-                    classified_map[y][x - 1] = Classification::Right;
-                }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Right;
-                }
-            }
-            (Direction::North, Pipe::NW) => {
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Right;
+    fn part_one(maze: &Maze) -> Option<u32> {
+        let mut m = maze.clone();
+        while m.step() {}
+        Some(m.steps / 2)
+    }
+
+    fn part_two(maze: &Maze) -> Option<u32> {
+        let mut m = maze.clone();
+        let mut classified_map =
+            vec![vec![Classification::Unvisited; m.pipes[0].len()]; m.pipes.len()];
+        classified_map[m.current.1][m.current.0] = Classification::Pipe;
+
+        // 1 round to classify all pipes
+        while m.step() {
+            classified_map[m.current.1][m.current.0] = Classification::Pipe;
+        }
+
+        // 2nd round to find the areas to the left and the right of the walking path
+        m.direction = maze.direction.clone();
+        while m.step() {
+            let (x, y) = (m.current.0, m.current.1);
+            match (&m.direction, &m.pipes[m.current.1][m.current.0]) {
+                // Straight pipes
+                (Direction::North, Pipe::NS) => {
+                    if x > 0 && (classified_map[y][x - 1] == Classification::Unvisited) {
+                        classified_map[y][x - 1] = Classification::Left;
+                    }
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Right;
+                    }
                 }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Right;
+                (Direction::South, Pipe::NS) => {
+                    if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
+                        classified_map[y][x - 1] = Classification::Right;
+                    }
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Left;
+                    }
                 }
-            }
-            (Direction::West, Pipe::NW) => {
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Left;
+                (Direction::East, Pipe::EW) => {
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Left;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Right;
+                    }
                 }
-                if y < classified_map.len() - 1
-                    && classified_map[y + 1][x] == Classification::Unvisited
-                {
-                    classified_map[y + 1][x] = Classification::Left;
+                (Direction::West, Pipe::EW) => {
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Right;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Left;
+                    }
                 }
-            }
-            (Direction::East, Pipe::SE) => {
-                if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
-                    classified_map[y][x - 1] = Classification::Left;
+                // Corner pipes
+                (Direction::North, Pipe::NE) => {
+                    if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
+                        classified_map[y][x - 1] = Classification::Left;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Left;
+                    }
                 }
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Left;
+                (Direction::East, Pipe::NE) => {
+                    if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
+                        classified_map[y][x - 1] = Classification::Right;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Right;
+                    }
                 }
-            }
-            (Direction::South, Pipe::SE) => {
-                if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
-                    classified_map[y][x - 1] = Classification::Right;
+                (Direction::North, Pipe::NW) => {
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Right;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Right;
+                    }
                 }
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Right;
+                (Direction::West, Pipe::NW) => {
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Left;
+                    }
+                    if y < classified_map.len() - 1
+                        && classified_map[y + 1][x] == Classification::Unvisited
+                    {
+                        classified_map[y + 1][x] = Classification::Left;
+                    }
                 }
-            }
-            // new
-            (Direction::South, Pipe::SW) => {
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Left;
+                (Direction::East, Pipe::SE) => {
+                    if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
+                        classified_map[y][x - 1] = Classification::Left;
+                    }
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Left;
+                    }
                 }
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Left;
+                (Direction::South, Pipe::SE) => {
+                    if x > 0 && classified_map[y][x - 1] == Classification::Unvisited {
+                        classified_map[y][x - 1] = Classification::Right;
+                    }
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Right;
+                    }
                 }
-            }
-            (Direction::West, Pipe::SW) => {
-                if x < classified_map[y].len() - 1
-                    && classified_map[y][x + 1] == Classification::Unvisited
-                {
-                    classified_map[y][x + 1] = Classification::Right;
+                // new
+                (Direction::South, Pipe::SW) => {
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Left;
+                    }
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Left;
+                    }
                 }
-                if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
-                    classified_map[y - 1][x] = Classification::Right;
+                (Direction::West, Pipe::SW) => {
+                    if x < classified_map[y].len() - 1
+                        && classified_map[y][x + 1] == Classification::Unvisited
+                    {
+                        classified_map[y][x + 1] = Classification::Right;
+                    }
+                    if y > 0 && classified_map[y - 1][x] == Classification::Unvisited {
+                        classified_map[y - 1][x] = Classification::Right;
+                    }
                 }
+                _ => panic!("Invalid pipe at {:?}", m.current),
             }
-            _ => panic!("Invalid pipe at {:?}", m.current),
         }
-    }
 
-    // This is inefficient (multiple loops over the map) but I don't feel like
-    // fighting the borrow checker right now
-    let mut unclassified = true;
-    while unclassified {
-        unclassified = false;
-        for y in 0..classified_map.len() {
-            for x in 0..classified_map[y].len() {
-                match classified_map[y][x] {
-                    Classification::Left | Classification::Right => {
-                        let mut n = vec![];
-                        if x > 0 {
-                            n.push((x - 1, y));
-                        }
-                        if x < classified_map[y].len() - 1 {
-                            n.push((x + 1, y));
-                        }
-                        if y > 0 {
-                            n.push((x, y - 1));
-                        }
-                        if y < classified_map.len() - 1 {
-                            n.push((x, y + 1));
-                        }
-                        let mut n = n
-                            .into_iter()
-                            .filter(|(x, y)| {
-                                *x < classified_map[0].len()
-                                    && *y < classified_map.len()
-                                    && x >= &0
-                                    && y >= &0
-                                    && classified_map[*y][*x] == Classification::Unvisited
-                            })
-                            .collect::<Vec<(usize, usize)>>();
+        // This is inefficient (multiple loops over the map) but I don't feel like
+        // fighting the borrow checker right now
+        let mut unclassified = true;
+        while unclassified {
+            unclassified = false;
+            for y in 0..classified_map.len() {
+                for x in 0..classified_map[y].len() {
+                    match classified_map[y][x] {
+                        Classification::Left | Classification::Right => {
+                            let mut n = vec![];
+                            if x > 0 {
+                                n.push((x - 1, y));
+                            }
+                            if x < classified_map[y].len() - 1 {
+                                n.push((x + 1, y));
+                            }
+                            if y > 0 {
+                                n.push((x, y - 1));
+                            }
+                            if y < classified_map.len() - 1 {
+                                n.push((x, y + 1));
+                            }
+                            let mut n = n
+                                .into_iter()
+                                .filter(|(x, y)| {
+                                    *x < classified_map[0].len()
+                                        && *y < classified_map.len()
+                                        && x >= &0
+                                        && y >= &0
+                                        && classified_map[*y][*x] == Classification::Unvisited
+                                })
+                                .collect::<Vec<(usize, usize)>>();
 
-                        while let Some((x2, y2)) = n.pop() {
-                            classified_map[y2][x2] = classified_map[y][x].clone();
+                            while let Some((x2, y2)) = n.pop() {
+                                classified_map[y2][x2] = classified_map[y][x].clone();
+                            }
                         }
+                        Classification::Unvisited => unclassified = true,
+                        _ => {}
                     }
-                    Classification::Unvisited => unclassified = true,
-                    _ => {}
                 }
             }
         }
-    }
 
-    // count all pipe classifications
-    let counts = classified_map
-        .iter()
-        .map(|row| {
-            row.iter().fold(
-                (0, 0),
-                |(left, right), classification| match classification {
-                    Classification::Left => (left + 1, right),
-                    Classification::Right => (left, right + 1),
-                    _ => (left, right),
-                },
-            )
-        })
-        .reduce(|(left, right), (left2, right2)| (left + left2, right + right2))
-        .unwrap();
-    if classified_map[0][0] == Classification::Left {
-        Some(counts.1)
-    } else {
-        Some(counts.0)
+        // count all pipe classifications
+        let counts = classified_map
+            .iter()
+            .map(|row| {
+                row.iter().fold(
+                    (0, 0),
+                    |(left, right), classification| match classification {
+                        Classification::Left => (left + 1, right),
+                        Classification::Right => (left, right + 1),
+                        _ => (left, right),
+                    },
+                )
+            })
+            .reduce(|(left, right), (left2, right2)| (left + left2, right + right2))
+            .unwrap();
+        if classified_map[0][0] == Classification::Left {
+            Some(counts.1)
+        } else {
+            Some(counts.0)
+        }
     }
 }
 
-#[derive(Debug)]
-struct Maze {
+#[derive(Debug, Clone)]
+pub struct Maze {
     pipes: Vec<Vec<Pipe>>,
     current: (usize, usize),
     direction: Direction,
@@ -269,11 +284,11 @@ impl Maze {
             },
             Pipe::Ground => panic!("Fell off the maze at {:?}", self.current),
         }
-        return true;
+        true
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Direction {
     North,
     East,
@@ -281,7 +296,7 @@ enum Direction {
     West,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Pipe {
     NS,
     EW,
@@ -317,8 +332,27 @@ impl From<char> for Pipe {
     }
 }
 
+/// Finds a direction from the start into a pipe that connects back to it.
+fn start_direction(pipes: &[Vec<Pipe>], (x, y): (usize, usize)) -> Option<Direction> {
+    let pipe_at = |x: Option<usize>, y: Option<usize>| pipes.get(y?)?.get(x?);
+
+    if let Some(Pipe::EW | Pipe::NE | Pipe::SE) = pipe_at(x.checked_sub(1), Some(y)) {
+        return Some(Direction::West);
+    }
+    if let Some(Pipe::NS | Pipe::SE | Pipe::SW) = pipe_at(Some(x), y.checked_sub(1)) {
+        return Some(Direction::North);
+    }
+    if let Some(Pipe::EW | Pipe::NW | Pipe::SW) = pipe_at(Some(x + 1), Some(y)) {
+        return Some(Direction::East);
+    }
+    if let Some(Pipe::NS | Pipe::NE | Pipe::NW) = pipe_at(Some(x), Some(y + 1)) {
+        return Some(Direction::South);
+    }
+    None
+}
+
 impl FromStr for Maze {
-    type Err = ();
+    type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut start = (0, 0);
         let pipes = s
@@ -332,28 +366,14 @@ impl FromStr for Maze {
                 }
             })
         });
+        let direction = start_direction(&pipes, start).ok_or("The start is not connected")?;
         Ok(Maze {
             pipes,
             current: start,
-            direction: Direction::West,
+            direction,
             steps: 0,
         })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
+advent_of_code::example_tests!();
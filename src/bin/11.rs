@@ -92,19 +92,15 @@ impl Map {
     }
 }
 
+advent_of_code::example_tests!();
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(374));
-    }
-
     #[test]
     fn test_part_two() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", DAY).unwrap();
         let m = Map::new(input, 100);
         let result: Option<u128> = m
             .galaxies
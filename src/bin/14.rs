@@ -25,17 +25,14 @@ pub fn part_two(input: &str) -> Option<u32> {
     loads.get(remainder - 1).copied()
 }
 
-fn find_pattern_length(input: &Vec<u32>, min_length: u32) -> Option<usize> {
+fn find_pattern_length(input: &[u32], min_length: u32) -> Option<usize> {
     let mut pattern = Vec::new();
     for i in 0..input.len() {
         pattern.push(input[i]);
-        if pattern.len() >= min_length as usize {
-            if (0..pattern.len())
-                .map(|j| pattern[j] == input[pattern.len() + j])
-                .all(|x| x)
-            {
-                return Some(pattern.len());
-            }
+        if pattern.len() >= min_length as usize
+            && (0..pattern.len()).all(|j| pattern[j] == input[pattern.len() + j])
+        {
+            return Some(pattern.len());
         }
     }
     None
@@ -95,19 +92,4 @@ impl Mirror {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
-    }
-}
+advent_of_code::example_tests!();
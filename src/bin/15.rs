@@ -8,12 +8,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 // rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
 pub fn part_two(input: &str) -> Option<u32> {
-    let input = input
-        .trim()
-        .split(',')
-        .map(Instruction::from_str)
-        .filter(Result::is_ok)
-        .map(Result::unwrap);
+    let input = input.trim().split(',').flat_map(Instruction::from_str);
 
     let mut state = vec![Vec::<Lens>::new(); 256];
     for ins in input {
@@ -89,20 +84,4 @@ impl FromStr for Instruction {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        println!("{:?}", hash("qp"));
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1320));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(145));
-    }
-}
+advent_of_code::example_tests!();
@@ -55,7 +55,7 @@ impl State {
     // Possible next nodes:
     // Always make a turn left/right
     // Step forward from min..=max steps
-    fn successors(&self, min: usize, max: usize, board: &Vec<Vec<u8>>) -> Vec<(Self, u32)> {
+    fn successors(&self, min: usize, max: usize, board: &[Vec<u8>]) -> Vec<(Self, u32)> {
         let mut result = Vec::new();
         let mut directions = vec![self.facing.turn_left(), self.facing.turn_right()];
         if self.pos.x == 0 && self.pos.y == 0 {
@@ -69,7 +69,7 @@ impl State {
                         continue;
                     }
                     let state = Self::new(pos, direction);
-                    result.push((state, self.pos.cost(&pos, &board))); // TODO: Cost
+                    result.push((state, self.pos.cost(&pos, board))); // TODO: Cost
                 }
             }
         }
@@ -103,7 +103,7 @@ impl Pos {
         }
     }
 
-    fn cost(&self, to: &Pos, board: &Vec<Vec<u8>>) -> u32 {
+    fn cost(&self, to: &Pos, board: &[Vec<u8>]) -> u32 {
         let mut cost = 0;
         let mut pos = *self;
         while pos != *to {
@@ -164,19 +164,4 @@ impl Direction {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(102));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
+advent_of_code::example_tests!();
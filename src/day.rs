@@ -1,8 +1,12 @@
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+use crate::year::MAX_DAYS;
+
+/// A valid day number of advent (i.e. an integer in range 1 to the last day of the longest event).
+/// Not every event is that long, see [`Year::days`](crate::Year::days) for the days of one year.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -12,14 +16,15 @@ use std::str::FromStr;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
 pub struct Day(u8);
 
 impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
             return None;
         }
         Some(Self(day))
@@ -31,8 +36,14 @@ impl Day {
         Self(day)
     }
 
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __max() -> Self {
+        Self(MAX_DAYS)
+    }
+
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
@@ -55,6 +66,20 @@ impl PartialOrd<u8> for Day {
     }
 }
 
+impl TryFrom<u8> for Day {
+    type Error = DayFromStrError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::new(day).ok_or(DayFromStrError)
+    }
+}
+
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.0
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Day {
@@ -74,26 +99,37 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        f.write_str("expecting a day number of an event, starting at 1")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the longest events, see [`Year::days`](crate::Year::days)
+/// for the days of one year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of the longest events,
+/// or to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(Day(MAX_DAYS))
+    }
+
+    /// An iterator that yields every day from the 1st to `last`.
+    pub fn until(last: Day) -> Self {
+        Self {
+            current: 1,
+            last: last.0,
+        }
     }
 }
 
@@ -101,10 +137,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `last`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -115,15 +151,34 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+///
+/// Pass a year as first argument to check the day against the calendar of that year,
+/// e.g. `day!(2025, 12)`. Without it, any day of the longest events is accepted.
+///
+/// ```compile_fail
+/// # use advent_of_code::day;
+/// let day = day!(2025, 13);
+/// ```
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::Day::__max().into_inner(),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, expecting a day of an event"
+            ),
+        );
+        $crate::Day::__new_unchecked($day)
+    }};
+    ($year:expr, $day:expr) => {{
+        const _ASSERT: () = assert!(
+            $day != 0 && $day <= $crate::Year::__new_unchecked($year).last_day().into_inner(),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a day of the event of the year"
             ),
         );
         $crate::Day::__new_unchecked($day)
@@ -0,0 +1,184 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{all_days, Day, Year};
+
+/// A selection of days, parsed from a comma-separated list of days and ranges.
+/// A range without an end runs until the last day of the event.
+///
+/// ```
+/// # use advent_of_code::{day, year, DaySet};
+/// let days: DaySet = "1-3,15,20-".parse().unwrap();
+/// assert!(days.contains(day!(2)));
+/// assert!(!days.contains(day!(4)));
+/// assert_eq!(days.days(year!(2023)).count(), 10);
+/// assert_eq!(days.to_string(), "1-3,15,20-");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<(Day, Option<Day>)>);
+
+impl DaySet {
+    /// Every day of the event.
+    pub fn all() -> Self {
+        Self(vec![(Day::__new_unchecked(1), None)])
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0
+            .iter()
+            .any(|(start, end)| *start <= day && end.is_none_or(|end| day <= end))
+    }
+
+    /// Whether this set selects every day of any event.
+    pub fn is_all(&self) -> bool {
+        all_days().all(|day| self.contains(day))
+    }
+
+    /// An iterator that yields the selected days of the longest events.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+
+    /// An iterator that yields the selected days of the event of `year`.
+    pub fn days(&self, year: Year) -> impl Iterator<Item = Day> + '_ {
+        year.days().filter(|day| self.contains(*day))
+    }
+
+    /// The last day that was explicitly given, ranges without an end are ignored.
+    pub fn last_listed(&self) -> Option<Day> {
+        self.0
+            .iter()
+            .map(|(start, end)| end.unwrap_or(*start))
+            .max()
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(vec![(day, Some(day))])
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (start, end)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            let start = start.into_inner();
+            match end.map(Day::into_inner) {
+                Some(end) if end == start => write!(f, "{start}")?,
+                Some(end) => write!(f, "{start}-{end}")?,
+                None => write!(f, "{start}-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DaySetFromStrError(s.into());
+
+        let ranges = s
+            .split(',')
+            .map(|part| {
+                let part = part.trim();
+                match part.split_once('-') {
+                    Some((start, end)) => {
+                        let start: Day = if start.is_empty() {
+                            Day::__new_unchecked(1)
+                        } else {
+                            start.parse().map_err(|_| error())?
+                        };
+                        let end: Option<Day> = if end.is_empty() {
+                            None
+                        } else {
+                            Some(end.parse().map_err(|_| error())?)
+                        };
+                        if end.is_some_and(|end| end < start) {
+                            return Err(error());
+                        }
+                        Ok((start, end))
+                    }
+                    None => {
+                        let day: Day = part.parse().map_err(|_| error())?;
+                        Ok((day, Some(day)))
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self(ranges))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days `{}`, expecting day numbers or ranges like `1-10,15,20-`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::{day, year, Day};
+
+    fn days(s: &str) -> Vec<u8> {
+        let set: DaySet = s.parse().unwrap();
+        set.iter().map(Day::into_inner).collect()
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(days("3"), vec![3]);
+        assert_eq!(days("1-3, 5"), vec![1, 2, 3, 5]);
+        assert_eq!(days("23-"), vec![23, 24, 25]);
+        assert_eq!(days("-2"), vec![1, 2]);
+        assert!(DaySet::all().is_all());
+        assert!(!"1-24".parse::<DaySet>().unwrap().is_all());
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        for s in ["", "0", "26", "5-3", "1,,2", "a-b"] {
+            assert!(s.parse::<DaySet>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn limits_days_to_event() {
+        let set: DaySet = "10-".parse().unwrap();
+        assert_eq!(set.days(year!(2025)).count(), 3);
+        assert_eq!(set.last_listed(), Some(day!(10)));
+        assert_eq!(
+            "5,8-9".parse::<DaySet>().unwrap().last_listed(),
+            Some(day!(9))
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
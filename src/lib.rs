@@ -1,5 +1,10 @@
 pub mod aoc_lib;
 mod day;
+mod day_set;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use day_set::*;
+pub use template::solution::Solution;
+pub use year::*;
@@ -1,56 +1,183 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, start, verify, wait,
+};
 use args::{parse, AppArguments};
 
+// solutions are included as modules, their `main` functions are never called.
+#[allow(dead_code)]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
 mod args {
-    use std::process;
+    use std::{env, path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            commands::scaffold,
+            input::InputSource,
+            latest_scaffolded_day,
+            release::{self, Clock, SystemClock},
+            stats::Statistic,
+        },
+        Day, DaySet, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: DaySet,
         },
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            block: Option<usize>,
+            force: bool,
+        },
         Scaffold {
+            days: DaySet,
+            options: scaffold::Options,
+        },
+        Start {
             day: Day,
+            options: scaffold::Options,
+        },
+        Wait {
+            options: scaffold::Options,
         },
         Solve {
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            force: bool,
+            format: Option<String>,
+            input: InputSource,
         },
         All {
             release: bool,
             time: bool,
+            jobs: usize,
+            stat: Statistic,
+            threshold: Option<f64>,
+            days: DaySet,
         },
+        Verify {
+            days: DaySet,
+        },
+    }
+
+    /// Parses the subcommand and the year it applies to.
+    /// The year defaults to the main year of `aoc.toml` and can be overridden with `--year` on every subcommand.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
+        let defaults = DayDefaults {
+            clock: &SystemClock,
+            fallback: latest_scaffolded_day,
+        };
+        parse_from(pico_args::Arguments::from_env(), &defaults)
+    }
+
+    /// How the day of a command that was run without one is resolved, see [`default_day`].
+    pub struct DayDefaults<'a> {
+        /// Tells whether a puzzle of the year is released today.
+        pub clock: &'a dyn Clock,
+        /// The day used outside of the event, the latest scaffolded day.
+        pub fallback: fn(Year) -> Option<Day>,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Parses the arguments of a command. Flags and options are parsed before free-standing
+    /// arguments like days, as pico-args takes free-standing arguments in order.
+    pub fn parse_from(
+        mut args: pico_args::Arguments,
+        defaults: &DayDefaults,
+    ) -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
+        let subcommand = args.subcommand()?;
+
+        // set before the config is first read, solution binaries inherit it when run by `solve`.
+        if let Some(config) = args.opt_value_from_str::<_, PathBuf>("--config")? {
+            env::set_var("AOC_CONFIG", env::current_dir()?.join(config));
+        }
+
+        // parsed before the day, so its value is not taken for a free-standing argument.
+        let year = match args.opt_value_from_str("--year")?.or_else(Year::main) {
+            Some(year) => year,
+            None => {
+                eprintln!("No year specified. Pass \"--year\" or set \"year\" in \"aoc.toml\".");
+                process::exit(1);
+            }
+        };
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let release = args.contains("--release");
+                let time = args.contains("--time");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                // the baseline is only compared for release benchmarks, see `all::handle`.
+                if threshold.is_some() && !(release && time) {
+                    return Err(
+                        "\"--threshold\" needs a release benchmark, use `cargo time --threshold <percent>`."
+                            .into(),
+                    );
+                }
+                AppArguments::All {
+                    release,
+                    time,
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    stat: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                    threshold,
+                    days: parse_days(&mut args, year)?.unwrap_or_default(),
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                days: parse_days(&mut args, year)?.unwrap_or_default(),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: match parse_days(&mut args, year)? {
+                    Some(days) => days,
+                    None => DaySet::from(default_day(year, defaults)?),
+                },
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args, year, defaults)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                block: args.opt_value_from_str("--block")?,
+                force: args.contains("--force"),
+                day: parse_day(&mut args, year, defaults)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                options: parse_scaffold_options(&mut args)?,
+                days: match parse_days(&mut args, year)? {
+                    Some(days) => days,
+                    None => DaySet::from(default_day(year, defaults)?),
+                },
+            },
+            Some("start") => AppArguments::Start {
+                options: parse_scaffold_options(&mut args)?,
+                day: parse_day(&mut args, year, defaults)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            Some("wait") => AppArguments::Wait {
+                options: parse_scaffold_options(&mut args)?,
             },
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let force = args.contains("--force");
+                let format = args.opt_value_from_str("--format")?;
+                let time = args.contains("--time");
+                // parsed last, as the day, `--example [N]` and `-` are free-standing arguments.
+                let (day, input) = parse_day_and_input_source(&mut args, year, defaults)?;
+                AppArguments::Solve {
+                    day,
+                    release,
+                    time,
+                    submit,
+                    force,
+                    format,
+                    input,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -66,7 +193,123 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+
+    /// Parses a day of the event of `year`, or resolves the default day if none is given.
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+        defaults: &DayDefaults,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        resolve_day(args.opt_free_from_str()?, year, defaults)
+    }
+
+    /// Checks that a given day is part of the event of `year`, or resolves the default day.
+    fn resolve_day(
+        day: Option<Day>,
+        year: Year,
+        defaults: &DayDefaults,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let day = match day {
+            Some(day) => day,
+            None => default_day(year, defaults)?,
+        };
+        if !year.has_day(day) {
+            return Err(format!("the {year} event ends on day {}.", year.last_day()).into());
+        }
+        Ok(day)
+    }
+
+    /// Parses an optional set of days like `1-10,15,20-`.
+    /// Days that are listed explicitly have to be part of the event of `year`.
+    fn parse_days(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<DaySet>, Box<dyn std::error::Error>> {
+        let days: Option<DaySet> = args.opt_free_from_str()?;
+        if let Some(day) = days.as_ref().and_then(DaySet::last_listed) {
+            if !year.has_day(day) {
+                return Err(format!("the {year} event ends on day {}.", year.last_day()).into());
+            }
+        }
+        Ok(days)
+    }
+
+    /// The day of a command that was run without one: today's puzzle during December,
+    /// the latest scaffolded day otherwise. Printed, so it is clear which day is used.
+    fn default_day(year: Year, defaults: &DayDefaults) -> Result<Day, Box<dyn std::error::Error>> {
+        if let Some(day) = release::puzzle_day(year, defaults.clock.now()) {
+            eprintln!("No day given, using today's puzzle: day {day}.");
+            return Ok(day);
+        }
+        if let Some(day) = (defaults.fallback)(year) {
+            eprintln!("No day given, using the latest scaffolded day: day {day}.");
+            return Ok(day);
+        }
+        Err(format!("no day given and no day of {year} is scaffolded yet.").into())
+    }
+
+    /// Parses `--template NAME`, `--type TYPE` and any number of `--import PATH`.
+    fn parse_scaffold_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<scaffold::Options, pico_args::Error> {
+        let defaults = scaffold::Options::default();
+        let imports: Vec<String> = args.values_from_str("--import")?;
+
+        Ok(scaffold::Options {
+            template: args
+                .opt_value_from_str("--template")?
+                .unwrap_or(defaults.template),
+            return_type: args
+                .opt_value_from_str("--type")?
+                .unwrap_or(defaults.return_type),
+            imports: imports
+                .iter()
+                .flat_map(|paths| paths.split(','))
+                .map(|path| path.trim().to_string())
+                .filter(|path| !path.is_empty())
+                .collect(),
+        })
+    }
+
+    /// Parses `[day] [--input <path> | --example [N] | -]`. The day comes first, so it has to be
+    /// given explicitly together with an example number.
+    fn parse_day_and_input_source(
+        args: &mut pico_args::Arguments,
+        year: Year,
+        defaults: &DayDefaults,
+    ) -> Result<(Day, InputSource), Box<dyn std::error::Error>> {
+        let mut source_args = vec![];
+
+        if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+            source_args.extend(["--input".into(), path]);
+        }
+        let is_example = args.contains("--example");
+
+        let mut free: Vec<String> = vec![];
+        while free.len() < 2 {
+            match args.opt_free_from_str::<String>()? {
+                Some(arg) => free.push(arg),
+                None => break,
+            }
+        }
+
+        let day = match free.first().filter(|arg| *arg != "-") {
+            Some(arg) => Some(arg.parse::<Day>()?),
+            None => None,
+        };
+        if day.is_some() {
+            free.remove(0);
+        }
+        let day = resolve_day(day, year, defaults)?;
+
+        if is_example {
+            source_args.push("--example".into());
+        }
+        source_args.extend(free);
+
+        Ok((day, InputSource::from_args(&source_args)?))
     }
 }
 
@@ -76,17 +319,153 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+        Ok((year, args)) => match args {
+            AppArguments::All {
+                release,
+                time,
+                jobs,
+                stat,
+                threshold,
+                days,
+            } => all::handle(
+                year,
+                &registry::solutions(year),
+                &days,
+                release,
+                time,
+                jobs,
+                stat,
+                threshold,
+            ),
+            AppArguments::Verify { days } => {
+                verify::handle(year, &registry::solutions(year), &days)
+            }
+            AppArguments::Download { days } => download::handle(year, &days),
+            AppArguments::Read { day } => read::handle(year, day),
+            AppArguments::Examples { day, block, force } => {
+                examples::handle(year, day, block, force)
+            }
+            AppArguments::Scaffold { days, options } => scaffold::handle(year, &days, &options),
+            AppArguments::Start { day, options } => start::handle(year, day, &options),
+            AppArguments::Wait { options } => wait::handle(year, &options),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                force,
+                format,
+                input,
+            } => solve::handle(year, day, release, time, submit, force, format, &input),
         },
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::args::{parse_from, AppArguments, DayDefaults};
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::release::{release_time, Clock};
+    use advent_of_code::{day, year, Day, Year};
+    use std::time::{Duration, SystemTime};
+
+    /// A clock that always returns the same time.
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+
+        fn sleep(&self, _: Duration) {}
+    }
+
+    fn parse(
+        args: &[&str],
+        now: SystemTime,
+    ) -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
+        let defaults = DayDefaults {
+            clock: &FixedClock(now),
+            fallback: |_| Some(day!(7)),
+        };
+        let args = args.iter().map(Into::into).collect();
+        parse_from(pico_args::Arguments::from_vec(args), &defaults)
+    }
+
+    /// Parses `solve` with arguments of 2023, after its event ended.
+    fn parse_solve(args: &[&str]) -> (Day, bool, Option<u8>, InputSource) {
+        let args: Vec<&str> = ["solve", "--year", "2023"]
+            .iter()
+            .chain(args)
+            .copied()
+            .collect();
+        let now = release_time(year!(2024), day!(1));
+        match parse(&args, now).unwrap() {
+            (
+                _,
+                AppArguments::Solve {
+                    day,
+                    time,
+                    submit,
+                    input,
+                    ..
+                },
+            ) => (day, time, submit, input),
+            _ => panic!("expecting the solve command"),
+        }
+    }
+
+    #[test]
+    fn rejects_threshold_without_release_benchmark() {
+        let parse = |args: &[&str]| {
+            let args: Vec<&str> = ["all", "--year", "2023"]
+                .iter()
+                .chain(args)
+                .copied()
+                .collect();
+            parse(&args, SystemTime::UNIX_EPOCH)
+        };
+
+        assert!(parse(&["--threshold", "5"]).is_err());
+        assert!(parse(&["--time", "--threshold", "5"]).is_err());
+        assert!(parse(&["--release", "--threshold", "5"]).is_err());
+        assert!(parse(&["--release", "--time", "--threshold", "5"]).is_ok());
+    }
+
+    #[test]
+    fn parses_flags_before_day() {
+        assert_eq!(
+            parse_solve(&["--time"]),
+            (day!(7), true, None, InputSource::Puzzle)
+        );
+        assert_eq!(
+            parse_solve(&["--submit", "1"]),
+            (day!(7), false, Some(1), InputSource::Puzzle)
+        );
+        assert_eq!(
+            parse_solve(&["3", "--time", "--submit", "2"]),
+            (day!(3), true, Some(2), InputSource::Puzzle)
+        );
+        assert_eq!(
+            parse_solve(&["--example", "3", "2"]),
+            (day!(3), false, None, InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            parse_solve(&["-"]),
+            (day!(7), false, None, InputSource::Stdin)
+        );
+    }
+
+    #[test]
+    fn defaults_to_todays_puzzle() {
+        let now = release_time(year!(2023), day!(12));
+        match parse(&["read", "--year", "2023"], now).unwrap() {
+            (_, AppArguments::Read { day }) => assert_eq!(day, day!(12)),
+            _ => panic!("expecting the read command"),
+        }
+
+        let now = release_time(year!(2025), day!(12));
+        assert!(parse(&["read", "--year", "2023", "13"], now).is_ok());
+        assert!(parse(&["read", "--year", "2025", "13"], now).is_err());
+    }
+}
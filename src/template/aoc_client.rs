@@ -0,0 +1,472 @@
+/// Minimal HTTP client for the Advent of Code website.
+/// Authenticates with the `session` cookie of a logged-in browser session.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::template::{data_dir, relative};
+use crate::{Day, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Transport(String),
+    BadStatus(u16, String),
+    UnexpectedResponse(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set \"AOC_SESSION\" or create \"~/.adventofcode.session\"."
+            ),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(status, url) => {
+                write!(f, "server responded with status {status} for \"{url}\".")
+            }
+            AocClientError::UnexpectedResponse(url) => {
+                write!(f, "could not find puzzle content in response for \"{url}\".")
+            }
+            AocClientError::IoError(e) => write!(f, "could not write output files: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                AocClientError::BadStatus(status, response.get_url().to_string())
+            }
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client for a year from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, falling back to `~/.adventofcode.session`.
+    ///  2. the base URL is read from `AOC_BASE_URL`, falling back to [`DEFAULT_BASE_URL`].
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    /// Fetches the personal puzzle input for a day.
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle description for a day and converts it to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let url = self.day_url(day);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        let html = response.into_string()?;
+        html_to_markdown(&html).ok_or(AocClientError::UnexpectedResponse(url))
+    }
+
+    /// Submits an answer for one part of a day and parses the server's reply.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        let html = response.into_string()?;
+        html_to_markdown(&html)
+            .as_deref()
+            .and_then(SubmissionOutcome::from_response)
+            .ok_or(AocClientError::UnexpectedResponse(url))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    AlreadySolved,
+    RateLimited(Duration),
+}
+
+impl SubmissionOutcome {
+    /// Parses the outcome from the text of an `/answer` response.
+    pub fn from_response(text: &str) -> Option<Self> {
+        lazy_static::lazy_static! {
+            static ref WAIT: Regex =
+                Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        }
+
+        if text.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Some(Self::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Incorrect)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = WAIT.captures(text).map_or(0, |c| {
+                let minutes = c.get(1).map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+                let seconds = c[2].parse::<u64>().unwrap();
+                minutes * 60 + seconds
+            });
+            Some(Self::RateLimited(Duration::from_secs(wait)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// The exit code a solution binary terminates with after submitting.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Correct => 0,
+            Self::Incorrect => 10,
+            Self::TooHigh => 11,
+            Self::TooLow => 12,
+            Self::AlreadySolved => 13,
+            Self::RateLimited(_) => 14,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "⭐️ That's the right answer!"),
+            Self::Incorrect => write!(f, "❌ That's not the right answer."),
+            Self::TooHigh => write!(f, "❌ That's not the right answer, it is too high."),
+            Self::TooLow => write!(f, "❌ That's not the right answer, it is too low."),
+            Self::AlreadySolved => write!(f, "🎄 This part has already been solved."),
+            Self::RateLimited(wait) => write!(
+                f,
+                "⏳ You gave an answer too recently, wait {}m {}s.",
+                wait.as_secs() / 60,
+                wait.as_secs() % 60
+            ),
+        }
+    }
+}
+
+/// Extracts the `<main>` element of a page and renders it as markdown.
+/// Code blocks are rendered as fenced blocks, forms and scripts are dropped.
+fn html_to_markdown(html: &str) -> Option<String> {
+    lazy_static::lazy_static! {
+        static ref MAIN: Regex = Regex::new(r"(?s)<main>(.*)</main>").unwrap();
+        static ref NOISE: Regex = Regex::new(r"(?s)<form.*?</form>|<script.*?</script>").unwrap();
+    }
+
+    let main = MAIN.captures(html)?.get(1)?.as_str();
+    let main = NOISE
+        .replace_all(main, "")
+        .replace("<pre><code>", "<pre>```\n")
+        .replace("</code></pre>", "```</pre>");
+
+    Some(html2text::from_read(main.as_bytes(), 80).trim().to_string())
+}
+
+pub fn get_input_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("inputs").join(format!("{day}.txt"))
+}
+
+pub fn get_puzzle_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("puzzles").join(format!("{day}.md"))
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+
+    [".adventofcode.session", ".config/adventofcode.session"]
+        .iter()
+        .map(|file| PathBuf::from(&home).join(file))
+        .find_map(|path| read_session_file(&path))
+}
+
+fn read_session_file(path: &Path) -> Option<String> {
+    let session = fs::read_to_string(path).ok()?;
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Downloads input and puzzle description of a day to the data directory of its year.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    let input = client.get_input(day)?;
+    let puzzle = client.get_puzzle(day)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        relative(&input_path).display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        relative(&puzzle_path).display()
+    );
+    Ok(())
+}
+
+/// Downloads only the input of a day, see [`download`].
+pub fn download_input(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+    let input_path = get_input_path(year, day);
+
+    write_file(&input_path, &client.get_input(day)?)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        relative(&input_path).display()
+    );
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, stores it and returns it as markdown.
+pub fn read(year: Year, day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env(year)?;
+    let puzzle = client.get_puzzle(day)?;
+    write_file(&get_puzzle_path(year, day), &puzzle)?;
+    Ok(puzzle)
+}
+
+pub fn submit(
+    year: Year,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocClientError> {
+    let client = AocClient::from_env(year)?;
+    client.submit(day, part, result)
+}
+
+/// Writes a file, creating the data directories of a new year if needed.
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, SubmissionOutcome};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// Serves a single canned response on a random local port.
+    /// The handle resolves to the raw request that was received.
+    fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (base_url, server) = serve_once("200 OK", "1abc2\n");
+        let client = AocClient::new(&base_url, "secret\n", year!(2023));
+
+        let input = client.get_input(day!(1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1abc2\n");
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<html><main><article><h2>--- Day 1: Trebuchet?! ---</h2>\
+             <pre><code>1abc2\npqr3stu8vwx\n</code></pre>\
+             <p>Produces <code><em>142</em></code>.</p></article>\
+             <p>Your puzzle answer was <code>54634</code>.</p>\
+             <form method=\"post\"><input name=\"answer\"/></form></main></html>",
+        );
+        let client = AocClient::new(&base_url, "secret", year!(2023));
+
+        let puzzle = client.get_puzzle(day!(1)).unwrap();
+        server.join().unwrap();
+
+        assert!(puzzle.starts_with("## --- Day 1: Trebuchet?! ---"));
+        assert!(puzzle.contains("```\n1abc2\npqr3stu8vwx\n```"));
+        assert!(puzzle.contains("Produces `*142*`."));
+        assert!(puzzle.contains("Your puzzle answer was `54634`."));
+        assert!(!puzzle.contains("answer\""));
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret", year!(2023));
+
+        let outcome = client.submit(day!(7), 2, "1234").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, server) = serve_once("404 Not Found", "not found");
+        let client = AocClient::new(&base_url, "secret", year!(2023));
+
+        let result = client.get_input(day!(3));
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::BadStatus(404, _))));
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        let parse = SubmissionOutcome::from_response;
+
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Some(SubmissionOutcome::Incorrect)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high. Please wait one minute."),
+            Some(SubmissionOutcome::TooHigh)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            Some(SubmissionOutcome::TooLow)
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level. Did you already complete it?"),
+            Some(SubmissionOutcome::AlreadySolved)
+        );
+        assert_eq!(
+            parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 39s left to wait."),
+            Some(SubmissionOutcome::RateLimited(Duration::from_secs(279)))
+        );
+        assert_eq!(
+            parse("You gave an answer too recently. You have 12s left to wait."),
+            Some(SubmissionOutcome::RateLimited(Duration::from_secs(12)))
+        );
+        assert_eq!(parse("To play, please identify yourself."), None);
+    }
+}
@@ -0,0 +1,297 @@
+/// Module that persists benchmark results per machine and compares new runs against them.
+/// Baselines are stored in `benchmarks/<machine>.json` of the data directory of a year.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::data_dir;
+use crate::template::runner::{PartRecord, PARSE};
+use crate::template::stats::Statistic;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark baseline: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark baseline: {e}"),
+        }
+    }
+}
+
+/// The benchmark results of one machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub machine: String,
+    #[serde(default)]
+    pub records: Vec<PartRecord>,
+}
+
+/// The difference between the baseline and the current timing of one part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+    /// Relative change in percent, positive if the part got slower.
+    pub change: f64,
+    /// Whether the 95% confidence intervals of the mean of both runs do not overlap in the
+    /// direction of `change`. Only the mean has a confidence interval, so it decides the
+    /// significance for every statistic, and a change of the statistic against it is not significant.
+    pub is_significant: bool,
+}
+
+impl Delta {
+    pub fn is_regression(&self) -> bool {
+        self.is_significant && self.change > 0.0
+    }
+
+    /// Whether this is a significant slowdown of more than `threshold` percent.
+    pub fn exceeds(&self, threshold: f64) -> bool {
+        self.is_regression() && self.change > threshold
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let step = if self.part == PARSE {
+            "parse".into()
+        } else {
+            format!("part {}", self.part)
+        };
+        write!(
+            f,
+            "Day {} {step}: {:.1?} → {:.1?} ({:+.1}%)",
+            self.day, self.before, self.after, self.change
+        )?;
+        if self.is_regression() {
+            write!(f, " ⚠ regression")?;
+        } else if self.is_significant {
+            write!(f, " ✔ improvement")?;
+        }
+        Ok(())
+    }
+}
+
+/// The name of this machine. Uses `AOC_MACHINE` if set, the hostname otherwise.
+#[must_use]
+pub fn machine_name() -> String {
+    let name = env::var("AOC_MACHINE")
+        .or_else(|_| env::var("HOSTNAME"))
+        .or_else(|_| fs::read_to_string("/etc/hostname"))
+        .unwrap_or_default();
+
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.is_empty() {
+        "default".into()
+    } else {
+        name
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year, machine: &str) -> PathBuf {
+    data_dir(year)
+        .join("benchmarks")
+        .join(format!("{machine}.json"))
+}
+
+impl Baseline {
+    pub fn new(machine: &str, records: Vec<PartRecord>) -> Self {
+        Self {
+            machine: machine.into(),
+            records,
+        }
+    }
+
+    /// Loads the baseline of a machine, if one was saved.
+    pub fn load(year: Year, machine: &str) -> Result<Option<Self>, Error> {
+        match fs::read_to_string(get_path(year, machine)) {
+            Ok(s) => serde_json::from_str(&s)
+                .map(Some)
+                .map_err(|e| Error::Parser(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, year: Year) -> Result<(), Error> {
+        let path = get_path(year, &self.machine);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| Error::Parser(e.to_string()))?;
+        fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// The records of this baseline with those of `days` replaced by `records`, sorted by day.
+    pub fn merged(&self, days: &[Day], records: Vec<PartRecord>) -> Vec<PartRecord> {
+        let mut merged: Vec<PartRecord> = self
+            .records
+            .iter()
+            .filter(|r| !days.contains(&r.day))
+            .cloned()
+            .chain(records)
+            .collect();
+        merged.sort_by_key(|r| (r.day, r.part));
+        merged
+    }
+
+    /// Compares `records` with the timings of the same parts in this baseline.
+    /// Parts that are missing from either side are skipped.
+    pub fn compare(&self, records: &[PartRecord], statistic: Statistic) -> Vec<Delta> {
+        records
+            .iter()
+            .filter_map(|current| {
+                let previous = self
+                    .records
+                    .iter()
+                    .find(|r| r.day == current.day && r.part == current.part)?;
+
+                let before = previous.duration_by(statistic);
+                let after = current.duration_by(statistic);
+                if before.is_zero() {
+                    return None;
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                let change = (after.as_nanos() as f64 / before.as_nanos() as f64 - 1.0) * 100.0;
+
+                let is_significant = match (&previous.stats, &current.stats) {
+                    (Some(previous), Some(current)) => {
+                        let is_slower = current.ci_low_nanos > previous.ci_high_nanos;
+                        let is_faster = current.ci_high_nanos < previous.ci_low_nanos;
+                        (is_slower && change > 0.0) || (is_faster && change < 0.0)
+                    }
+                    _ => false,
+                };
+
+                Some(Delta {
+                    day: current.day,
+                    part: current.part,
+                    before,
+                    after,
+                    change,
+                    is_significant,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Baseline;
+    use crate::day;
+    use crate::template::runner::{PartRecord, PartStatus};
+    use crate::template::stats::{BenchStats, Statistic};
+
+    fn record(part: u8, median_nanos: u64, ci: (u64, u64)) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            answer: Some("42".into()),
+            duration_nanos: median_nanos,
+            samples: 100,
+            status: PartStatus::Solved,
+            error: None,
+            stats: Some(BenchStats {
+                mean_nanos: median_nanos,
+                median_nanos,
+                min_nanos: ci.0,
+                max_nanos: ci.1,
+                std_dev_nanos: 0,
+                ci_low_nanos: ci.0,
+                ci_high_nanos: ci.1,
+                warmup: 10,
+                outliers: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn compares_with_baseline() {
+        let baseline = Baseline::new(
+            "test",
+            vec![record(1, 100, (95, 105)), record(2, 100, (95, 105))],
+        );
+
+        let deltas = baseline.compare(
+            &[record(1, 103, (98, 108)), record(2, 150, (140, 160))],
+            Statistic::Median,
+        );
+
+        assert_eq!(deltas.len(), 2);
+        assert!((deltas[0].change - 3.0).abs() < 1e-9);
+        assert!(!deltas[0].is_regression());
+        assert!((deltas[1].change - 50.0).abs() < 1e-9);
+        assert!(deltas[1].is_regression());
+        assert!(deltas[1].exceeds(10.0));
+        assert!(!deltas[1].exceeds(60.0));
+    }
+
+    #[test]
+    fn judges_significance_by_mean_confidence_interval() {
+        let baseline = Baseline::new("test", vec![record(1, 100, (95, 105))]);
+        let mut current = record(1, 150, (140, 160));
+
+        let deltas = baseline.compare(&[current.clone()], Statistic::Min);
+        assert!((deltas[0].change - 47.368).abs() < 1e-3);
+        assert!(deltas[0].is_regression());
+
+        // the minimum got faster while the mean got slower.
+        current.stats.as_mut().unwrap().min_nanos = 90;
+        let deltas = baseline.compare(&[current.clone()], Statistic::Min);
+        assert!(deltas[0].change < 0.0);
+        assert!(!deltas[0].is_significant);
+
+        let deltas = baseline.compare(&[current], Statistic::Mean);
+        assert!(deltas[0].is_regression());
+    }
+
+    #[test]
+    fn merges_records_of_other_days() {
+        let other_day = PartRecord {
+            day: day!(2),
+            ..record(1, 100, (95, 105))
+        };
+        let baseline = Baseline::new("test", vec![record(1, 100, (95, 105)), other_day]);
+
+        let merged = baseline.merged(&[day!(1)], vec![record(1, 50, (45, 55))]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].day, merged[0].duration_nanos), (day!(1), 50));
+        assert_eq!((merged[1].day, merged[1].duration_nanos), (day!(2), 100));
+    }
+
+    #[test]
+    fn skips_parts_missing_from_baseline() {
+        let baseline = Baseline::new("test", vec![record(1, 100, (95, 105))]);
+        let deltas = baseline.compare(&[record(2, 100, (95, 105))], Statistic::Median);
+        assert!(deltas.is_empty());
+    }
+}
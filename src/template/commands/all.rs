@@ -1,29 +1,67 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
 
 use crate::template::{
+    baseline::{self, Baseline},
     readme_benchmarks::{self, Timings},
+    registry::{self, Entry},
+    relative,
+    runner::{self, PartRecord},
+    stats::Statistic,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet, Year};
+
+/// Runs the registered solutions of `days` in-process on up to `jobs` threads.
+/// Benchmarking runs are always serialized so that timings are not distorted.
+/// Benchmarks are summarized by `statistic` in the total and the README table.
+/// Release benchmarks are compared with the baseline of this machine, a significant slowdown
+/// of more than `threshold` percent fails the command before the README and the baseline are updated.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    solutions: &[Entry],
+    days: &DaySet,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    statistic: Statistic,
+    threshold: Option<f64>,
+) {
+    let jobs = if is_timed && jobs > 1 {
+        eprintln!("Ignoring --jobs {jobs}: benchmarks run one day at a time.");
+        1
+    } else {
+        jobs.max(1)
+    };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+    let days: Vec<Day> = days.days(year).collect();
     let mut timings: Vec<Timings> = vec![];
+    let mut all_records: Vec<PartRecord> = vec![];
+
+    let run = |day| {
+        registry::find(solutions, day)
+            .map(|solution| solution.run(year, is_timed))
+            .unwrap_or_default()
+    };
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    run_days(&days, jobs, run, |day, records| {
+        if day > days[0] {
             println!();
         }
 
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-        if output.is_empty() {
+        if records.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            records.iter().for_each(runner::print_record);
+            timings.push(Timings::from_records(day, &records, statistic));
+            all_records.extend(records);
         }
     });
 
@@ -33,222 +71,183 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+            let machine = baseline::machine_name();
+            let previous = load_baseline(year, &machine);
+
+            // timings of the days that did not run are kept from the baseline.
+            let merged = match &previous {
+                Some(previous) => previous.merged(&days, all_records.clone()),
+                None => all_records.clone(),
+            };
+
+            if !compare_with_baseline(
+                &machine,
+                previous.as_ref(),
+                &all_records,
+                statistic,
+                threshold,
+            ) {
+                eprintln!("The README and the benchmark baseline were not updated.");
+                process::exit(1);
             }
-        }
-    }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
+            if days.len() < year.days().count() && previous.is_none() {
+                println!("Not updating README: no benchmark baseline with the timings of the other days.");
+            } else {
+                update_readme(year, &merged, statistic);
+            }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+            save_baseline(year, &machine, merged);
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+fn update_readme(year: Year, records: &[PartRecord], statistic: Statistic) {
+    let timings: Vec<Timings> = records
+        .chunk_by(|a, b| a.day == b.day)
+        .map(|records| Timings::from_records(records[0].day, records, statistic))
+        .collect();
+    let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+
+    match readme_benchmarks::update(year, timings, total_millis) {
+        Ok(()) => println!("Successfully updated README with benchmarks."),
+        Err(_) => {
+            eprintln!("Failed to update readme with benchmarks.");
         }
+    }
+}
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
+fn load_baseline(year: Year, machine: &str) -> Option<Baseline> {
+    match Baseline::load(year, machine) {
+        Ok(Some(previous)) => Some(previous),
+        Ok(None) => {
+            println!("No benchmark baseline for \"{machine}\" yet.");
+            None
         }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        Err(e) => {
+            eprintln!("{e}");
+            None
         }
+    }
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+/// Prints how the benchmarks of this run changed compared with the previous baseline of this machine.
+/// Returns `false` if a part regressed beyond `threshold`.
+fn compare_with_baseline(
+    machine: &str,
+    previous: Option<&Baseline>,
+    records: &[PartRecord],
+    statistic: Statistic,
+    threshold: Option<f64>,
+) -> bool {
+    let deltas = previous.map_or_else(Vec::new, |previous| previous.compare(records, statistic));
+
+    if !deltas.is_empty() {
+        println!("\n{ANSI_BOLD}Compared with baseline \"{machine}\":{ANSI_RESET}");
+        deltas.iter().for_each(|delta| println!("{delta}"));
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    let Some(threshold) = threshold else {
+        return true;
+    };
 
-        Ok(output)
+    let failures: Vec<_> = deltas.iter().filter(|d| d.exceeds(threshold)).collect();
+    for delta in &failures {
+        eprintln!("{delta}: more than the allowed {threshold}%.");
     }
+    failures.is_empty()
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+/// Saves `merged` as the new baseline of this machine.
+fn save_baseline(year: Year, machine: &str, merged: Vec<PartRecord>) {
+    match Baseline::new(machine, merged).save(year) {
+        Ok(()) => println!(
+            "Saved benchmark baseline to \"{}\".",
+            relative(&baseline::get_path(year, machine)).display()
+        ),
+        Err(e) => eprintln!("{e}"),
+    }
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+/// Calls `run` for each of `days` on `jobs` worker threads.
+/// Results are buffered and `report` is called on the calling thread in the order of `days`.
+fn run_days(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> Vec<PartRecord> + Sync,
+    mut report: impl FnMut(Day, Vec<PartRecord>),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next = &next;
+            let run = &run;
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if sender.send((index, run(day))).is_err() {
+                    break;
                 }
-
-                timings.total_nanos += nanos;
             });
+        }
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+        // drop the original sender so the receiver hangs up once all workers are done.
+        drop(sender);
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        for (index, records) in receiver {
+            pending.insert(index, records);
+            while let Some(records) = pending.remove(&next_index) {
+                report(days[next_index], records);
+                next_index += 1;
+            }
         }
+    });
+}
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_days;
+    use crate::template::runner::{PartRecord, PartStatus};
+    use crate::{all_days, Day, Year};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn reports_in_day_order() {
+        let days: Vec<Day> = all_days().take(6).collect();
+
+        // earlier days finish last.
+        let run = |day: Day| {
+            thread::sleep(Duration::from_millis(
+                10 * (6 - u64::from(day.into_inner())),
+            ));
+            vec![PartRecord {
+                day,
+                part: 1,
+                answer: Some(day.to_string()),
+                duration_nanos: 0,
+                samples: 1,
+                status: PartStatus::Solved,
+                error: None,
+                stats: None,
+            }]
+        };
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let mut reported = vec![];
+        run_days(&days, 3, run, |day, records| {
+            assert_eq!(records[0].day, day);
+            reported.push(day);
+        });
+
+        assert_eq!(reported, days);
     }
 }
@@ -1,15 +1,30 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::ledger;
+use crate::{Day, DaySet, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Downloads each of `days`. Failed days are reported and the remaining days are still downloaded.
+pub fn handle(year: Year, days: &DaySet) {
+    let mut has_failed = false;
+
+    for day in days.days(year) {
+        if let Err(e) = download_day(year, day) {
+            eprintln!("failed to download day {day}: {e}");
+            has_failed = true;
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if has_failed {
         process::exit(1);
-    };
+    }
+}
+
+/// Downloads the input and puzzle of a day and imports the answers it already shows.
+pub fn download_day(year: Year, day: Day) -> Result<(), AocClientError> {
+    aoc_client::download(year, day)?;
+
+    if let Err(e) = ledger::import_from_puzzle(year, day) {
+        eprintln!("failed to import answers from puzzle: {e}");
+    }
+    Ok(())
 }
@@ -0,0 +1,193 @@
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+};
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::examples::{self, CodeBlock, Example, Manifest};
+use crate::template::{data_dir, input::download_command, relative};
+use crate::{Day, Year};
+
+/// Writes a code block of the puzzle description to `examples/NN.txt` and records its expected answers.
+/// A block of part two is written to `examples/NN-2.txt`, so it does not replace the example of part one.
+/// `block` selects the code block (1-based). Without it, the block is asked for in a terminal
+/// and the first block is used otherwise.
+pub fn handle(year: Year, day: Day, block: Option<usize>, force: bool) {
+    if let Err(e) = extract(year, day, block, force) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Extracts the example of a day, see [`handle`].
+pub fn extract(year: Year, day: Day, block: Option<usize>, force: bool) -> Result<(), String> {
+    let puzzle_path = get_puzzle_path(year, day);
+    let puzzle = fs::read_to_string(&puzzle_path).map_err(|e| {
+        format!(
+            "could not read \"{}\": {e}. Run `{}` to download it.",
+            relative(&puzzle_path).display(),
+            download_command(Some(year), day)
+        )
+    })?;
+
+    let blocks = examples::code_blocks(&puzzle);
+    if blocks.is_empty() {
+        return Err(format!("the puzzle of day {day} has no code blocks."));
+    }
+
+    let index = match block {
+        Some(block) if (1..=blocks.len()).contains(&block) => block - 1,
+        Some(block) => {
+            return Err(format!(
+                "there is no code block {block}, the puzzle has {} blocks.",
+                blocks.len()
+            ))
+        }
+        None => choose_block(&blocks),
+    };
+    write_example(
+        &data_dir(year).join("examples"),
+        day,
+        &puzzle,
+        &blocks[index],
+        force,
+    )
+}
+
+/// Writes `block` to the examples directory `dir` and records its answers in the manifest of `day`.
+fn write_example(
+    dir: &Path,
+    day: Day,
+    puzzle: &str,
+    block: &CodeBlock,
+    force: bool,
+) -> Result<(), String> {
+    // `--example 2` reads `NN-2.txt`.
+    let name = match block.part {
+        1 => day.to_string(),
+        part => format!("{day}-{part}"),
+    };
+    let example_path = dir.join(format!("{name}.txt"));
+    let existing = fs::read_to_string(&example_path).unwrap_or_default();
+    if !force && !existing.trim().is_empty() && existing != block.content {
+        return Err(format!(
+            "\"{}\" already contains a different example. Append `--force` to overwrite it.",
+            relative(&example_path).display()
+        ));
+    }
+
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(&example_path, &block.content).map_err(|e| e.to_string())?;
+    println!(
+        "🎄 Successfully wrote example to \"{}\".",
+        relative(&example_path).display()
+    );
+
+    let [part_1, part_2] = examples::answers_for_block(puzzle, block);
+    for (part, answer) in [(1, &part_1), (2, &part_2)] {
+        match answer {
+            Some(answer) => println!("Part {part}: expecting {answer}"),
+            None => println!("Part {part}: no expected answer found"),
+        }
+    }
+
+    let manifest_path = dir.join(format!("{day}.json"));
+    let mut manifest = Manifest::load_from(&manifest_path).map_err(|e| e.to_string())?;
+    manifest.insert(Example {
+        name,
+        part_1,
+        part_2,
+    });
+    manifest
+        .save_to(&manifest_path)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Asks which block is the example if there is more than one. Only asks when run in a terminal.
+fn choose_block(blocks: &[CodeBlock]) -> usize {
+    if blocks.len() == 1 || !io::stdin().is_terminal() {
+        return 0;
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let first_line = block.content.lines().next().unwrap_or_default();
+        let lines = block.content.lines().count();
+        println!(
+            "[{}] part {}: {first_line} ({lines} lines)",
+            i + 1,
+            block.part
+        );
+    }
+    eprint!("Which block is the example? [1] ");
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return 0;
+    }
+    answer
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|i| (1..=blocks.len()).contains(i))
+        .map_or(0, |i| i - 1)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::write_example;
+    use crate::day;
+    use crate::template::examples::{code_blocks, Example, Manifest};
+    use std::{env, fs};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+```
+1abc2
+```
+
+Adding these together produces `*12*`.
+
+## --- Part Two ---
+
+```
+two1nine
+```
+
+Adding these together produces `*29*`.";
+
+    #[test]
+    fn extracts_both_parts_in_turn() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let blocks = code_blocks(PUZZLE);
+
+        write_example(&dir, day!(1), PUZZLE, &blocks[0], false).unwrap();
+        write_example(&dir, day!(1), PUZZLE, &blocks[1], false).unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("01.txt")).unwrap(), "1abc2\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("01-2.txt")).unwrap(),
+            "two1nine\n"
+        );
+        assert_eq!(
+            Manifest::load_from(&dir.join("01.json")).unwrap().examples,
+            [
+                Example {
+                    name: "01".into(),
+                    part_1: Some("12".into()),
+                    part_2: None,
+                },
+                Example {
+                    name: "01-2".into(),
+                    part_1: None,
+                    part_2: Some("29".into()),
+                },
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,94 +1,279 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::examples::{self, Example, Manifest};
+use crate::template::{bin_path, config, data_dir, relative, root};
+use crate::{Day, DaySet, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// The name of the template that is used unless another one is chosen.
+pub const DEFAULT_TEMPLATE: &str = "default";
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// The built-in template, used unless the templates directory contains a `default.rs`.
+const MODULE_TEMPLATE: &str = r#"{{imports}}advent_of_code::solution!({{day}});
+
+pub fn part_one(input: &str) -> Option<{{type}}> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<{{type}}> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+advent_of_code::example_tests!();
+"#;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// How the module of a day is generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The name of a template in the templates directory, without extension.
+    pub template: String,
+    /// The return type of both parts, filled in for `{{type}}`.
+    pub return_type: String,
+    /// Paths in `advent_of_code::aoc_lib` to import, e.g. `math::lcm`.
+    pub imports: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.into(),
+            return_type: "u32".into(),
+            imports: vec![],
+        }
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Loads a template from the templates directory. Falls back to the built-in template
+/// if the default template was not customized.
+pub fn load_template(name: &str) -> Result<String, String> {
+    let dir = root().join(&config::get().templates_dir);
+    let path = dir.join(format!("{name}.rs"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name == DEFAULT_TEMPLATE => {
+            Ok(MODULE_TEMPLATE.into())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let mut names: Vec<String> = fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|entry| entry.path())
+                        .filter(|path| path.extension().is_some_and(|x| x == "rs"))
+                        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !names.iter().any(|n| n == DEFAULT_TEMPLATE) {
+                names.push(DEFAULT_TEMPLATE.into());
+            }
+            names.sort();
+            Err(format!(
+                "Template \"{name}\" not found in \"{}\". Available templates: {}.",
+                relative(&dir).display(),
+                names.join(", ")
+            ))
+        }
+        Err(e) => Err(format!(
+            "Failed to read template \"{}\": {e}",
+            relative(&path).display()
+        )),
     }
 }
-"#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+/// Fills in the placeholders of a template: `{{day}}`, `{{year}}`, `{{title}}`, `{{type}}`
+/// and `{{imports}}`. Imports are followed by a blank line, so `{{imports}}` is placed
+/// directly before the first line of code.
+fn render(template: &str, year: Year, day: Day, title: &str, options: &Options) -> String {
+    let mut imports: String = options
+        .imports
+        .iter()
+        .map(|path| format!("use advent_of_code::aoc_lib::{path};\n"))
+        .collect();
+    if !imports.is_empty() {
+        imports.push('\n');
+    }
+
+    template
+        .replace("{{imports}}", &imports)
+        .replace("{{day}}", &day.into_inner().to_string())
+        .replace("{{year}}", &year.to_string())
+        .replace("{{title}}", title)
+        .replace("{{type}}", &options.return_type)
+}
+
+/// Finds the title of a puzzle description, e.g. `Day 1: Trebuchet?!`.
+fn puzzle_title(puzzle: &str) -> Option<&str> {
+    puzzle
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("## --- ")?.strip_suffix(" ---"))
+}
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffolds each of `days` from the template chosen in `options`.
+/// Failed days are reported and the remaining days are still scaffolded.
+pub fn handle(year: Year, days: &DaySet, options: &Options) {
+    let template = load_template(&options.template).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let mut has_failed = false;
+    let mut last = None;
+
+    for day in days.days(year) {
+        match scaffold_day(year, day, &template, options) {
+            Ok(()) => last = Some(day),
+            Err(e) => {
+                eprintln!("{e}");
+                has_failed = true;
+            }
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    if let Some(day) = last {
+        println!("---");
+        print_solve_hint(year, day);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if has_failed {
+        process::exit(1);
     }
+}
+
+pub fn print_solve_hint(year: Year, day: Day) {
+    if year.is_main() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+/// Creates the module of a day from a loaded `template`, and its empty input, example and
+/// example manifest if they do not exist yet. Fails if the module exists.
+pub fn scaffold_day(year: Year, day: Day, template: &str, options: &Options) -> Result<(), String> {
+    let data = data_dir(year);
+    let input_path = data.join("inputs").join(format!("{day}.txt"));
+    let example_path = data.join("examples").join(format!("{day}.txt"));
+    let module_path = root().join(bin_path(year, day));
+
+    let mut file = safe_create_file(&module_path)
+        .map_err(|e| format!("Failed to create module file for day {day}: {e}"))?;
+
+    // the title is only known if the puzzle was downloaded before.
+    let puzzle = fs::read_to_string(get_puzzle_path(year, day)).unwrap_or_default();
+    let title =
+        puzzle_title(&puzzle).map_or_else(|| format!("Day {}", day.into_inner()), String::from);
+
+    file.write_all(render(template, year, day, &title, options).as_bytes())
+        .map_err(|e| format!("Failed to write module contents: {e}"))?;
+    println!(
+        "Created module file \"{}\"",
+        relative(&module_path).display()
+    );
+
+    if !input_path.exists() {
+        create_file(&input_path).map_err(|e| format!("Failed to create input file: {e}"))?;
+        println!(
+            "Created empty input file \"{}\"",
+            relative(&input_path).display()
+        );
+    }
+
+    if !example_path.exists() {
+        create_file(&example_path).map_err(|e| format!("Failed to create example file: {e}"))?;
+        println!(
+            "Created empty example file \"{}\"",
+            relative(&example_path).display()
+        );
+    }
+
+    let manifest_path = examples::get_path(year, day);
+    if !manifest_path.exists() {
+        let manifest = Manifest {
+            examples: vec![Example {
+                name: day.to_string(),
+                ..Example::default()
+            }],
+        };
+        manifest
+            .save(year, day)
+            .map_err(|e| format!("Failed to create example manifest: {e}"))?;
+        println!(
+            "Created example manifest \"{}\"",
+            relative(&manifest_path).display()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, render, Options, MODULE_TEMPLATE};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let options = Options {
+            return_type: "u64".into(),
+            imports: vec!["math::lcm".into()],
+            ..Options::default()
+        };
+        let module = render(MODULE_TEMPLATE, year!(2023), day!(8), "Day 8", &options);
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+        assert!(module.starts_with(
+            "use advent_of_code::aoc_lib::math::lcm;\n\nadvent_of_code::solution!(8);\n"
+        ));
+        assert!(module.contains("pub fn part_two(input: &str) -> Option<u64> {"));
+
+        let module = render(
+            "// {{title}}, {{year}}",
+            year!(2023),
+            day!(8),
+            "Day 8",
+            &options,
+        );
+        assert_eq!(module, "// Day 8, 2023");
+
+        let default = render(
+            MODULE_TEMPLATE,
+            year!(2023),
+            day!(8),
+            "",
+            &Options::default(),
+        );
+        assert!(default.starts_with(
+            "advent_of_code::solution!(8);\n\npub fn part_one(input: &str) -> Option<u32> {"
+        ));
+    }
+
+    #[test]
+    fn finds_puzzle_title() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Trebuchet?! ---\n\nSomething"),
+            Some("Day 1: Trebuchet?!")
+        );
+        assert_eq!(puzzle_title("no title"), None);
+    }
 }
@@ -1,25 +1,50 @@
-use std::process::{Command, Stdio};
-
-use crate::Day;
-
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+use std::process::{self, Command, Stdio};
+
+use crate::template::bin_name;
+use crate::template::input::InputSource;
+use crate::{Day, Year};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    format: Option<String>,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.push("--year".to_string());
+    cmd_args.push(year.to_string());
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if force {
+            cmd_args.push("--force".to_string());
+        }
     }
 
     if time {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(format) = format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format);
+    }
+
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +52,9 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // mirror the exit code of the solution so scripts can react to submission outcomes.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,118 @@
+use std::{fs, path::Path, process};
+
+use crate::template::aoc_client::{self, get_input_path, get_puzzle_path};
+use crate::template::commands::{examples, scaffold};
+use crate::template::{bin_path, data_dir, ledger, relative, root};
+use crate::{Day, Year};
+
+/// The outcome of a step: `Ok(None)` if it ran, `Ok(Some(reason))` if it was not needed.
+type StepResult = Result<Option<String>, String>;
+
+/// Sets up a day in one go: downloads its input and puzzle, scaffolds the solution and extracts
+/// the example. Each step only runs if needed, so `start` can be repeated, e.g. to fetch part two.
+/// Existing solutions and non-empty inputs are never overwritten.
+pub fn handle(year: Year, day: Day, options: &scaffold::Options) {
+    let template = scaffold::load_template(&options.template).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let steps: [(&str, &dyn Fn() -> StepResult); 4] = [
+        ("input", &|| download_input(year, day)),
+        ("puzzle", &|| read_puzzle(year, day)),
+        ("solution", &|| scaffold(year, day, &template, options)),
+        ("example", &|| extract_example(year, day)),
+    ];
+
+    let mut skipped = vec![];
+    let mut has_failed = false;
+
+    for (name, step) in steps {
+        match step() {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+                println!("Skipped {name}: {reason}");
+                skipped.push(name);
+            }
+            Err(e) => {
+                eprintln!("failed to set up {name} of day {day}: {e}");
+                has_failed = true;
+            }
+        }
+    }
+
+    println!("---");
+    if !skipped.is_empty() {
+        println!("Skipped steps: {}", skipped.join(", "));
+    }
+
+    if has_failed {
+        process::exit(1);
+    }
+    scaffold::print_solve_hint(year, day);
+}
+
+fn has_content(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty())
+}
+
+fn download_input(year: Year, day: Day) -> StepResult {
+    let path = get_input_path(year, day);
+    if has_content(&path) {
+        return Ok(Some(format!(
+            "\"{}\" is not empty.",
+            relative(&path).display()
+        )));
+    }
+
+    aoc_client::download_input(year, day).map_err(|e| e.to_string())?;
+    Ok(None)
+}
+
+/// Reads the puzzle if it was not downloaded yet, or again if it does not include part two.
+fn read_puzzle(year: Year, day: Day) -> StepResult {
+    let path = get_puzzle_path(year, day);
+    if fs::read_to_string(&path).is_ok_and(|puzzle| puzzle.contains("--- Part Two ---")) {
+        return Ok(Some(format!(
+            "\"{}\" already includes part two.",
+            relative(&path).display()
+        )));
+    }
+
+    aoc_client::read(year, day).map_err(|e| e.to_string())?;
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        relative(&path).display()
+    );
+
+    if let Err(e) = ledger::import_from_puzzle(year, day) {
+        eprintln!("failed to import answers from puzzle: {e}");
+    }
+    Ok(None)
+}
+
+fn scaffold(year: Year, day: Day, template: &str, options: &scaffold::Options) -> StepResult {
+    let path = root().join(bin_path(year, day));
+    if path.exists() {
+        return Ok(Some(format!(
+            "\"{}\" already exists.",
+            relative(&path).display()
+        )));
+    }
+
+    scaffold::scaffold_day(year, day, template, options)?;
+    Ok(None)
+}
+
+fn extract_example(year: Year, day: Day) -> StepResult {
+    let path = data_dir(year).join("examples").join(format!("{day}.txt"));
+    if has_content(&path) {
+        return Ok(Some(format!(
+            "\"{}\" is not empty.",
+            relative(&path).display()
+        )));
+    }
+
+    examples::extract(year, day, None, false)?;
+    Ok(None)
+}
@@ -0,0 +1,186 @@
+use std::process;
+
+use crate::template::registry::Entry;
+use crate::template::runner::{PartRecord, PARSE};
+use crate::template::{ledger, ANSI_BOLD, ANSI_RESET};
+use crate::{DaySet, Year};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass(String),
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing(Option<String>),
+    /// The part returned an error or panicked.
+    Error {
+        expected: Option<String>,
+        error: String,
+    },
+}
+
+impl Status {
+    fn new(expected: Option<String>, actual: Result<Option<String>, String>) -> Self {
+        match (expected, actual) {
+            (expected, Err(error)) => Status::Error { expected, error },
+            (None, Ok(actual)) => Status::Missing(actual),
+            (Some(expected), Ok(actual)) if actual.as_ref() == Some(&expected) => {
+                Status::Pass(expected)
+            }
+            (Some(expected), Ok(actual)) => Status::Fail { expected, actual },
+        }
+    }
+}
+
+/// Runs the registered solutions of `days` in-process and compares them with the known answers.
+pub fn handle(year: Year, solutions: &[Entry], days: &DaySet) {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    let solutions = solutions.iter().filter(|s| days.contains(s.day));
+    solutions.for_each(|solution| {
+        let day = solution.day;
+
+        if passed + failed + missing > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        for (part, status) in [1, 2].into_iter().zip(verify_day(year, solution)) {
+            match status {
+                Status::Pass(answer) => {
+                    passed += 1;
+                    println!("Part {part}: ✔ {answer}");
+                }
+                Status::Fail { expected, actual } => {
+                    failed += 1;
+                    let actual = actual.unwrap_or_else(|| "nothing".into());
+                    println!("Part {part}: ✖ expected {expected}, got {actual}");
+                }
+                Status::Missing(actual) => {
+                    missing += 1;
+                    let actual = actual.unwrap_or_else(|| "nothing".into());
+                    println!("Part {part}: ? no known answer, got {actual}");
+                }
+                Status::Error { expected, error } => {
+                    failed += 1;
+                    match expected {
+                        Some(expected) => {
+                            println!("Part {part}: ✖ expected {expected}, got failed: {error}");
+                        }
+                        None => println!("Part {part}: ✖ failed: {error}"),
+                    }
+                }
+            }
+        }
+    });
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} missing."
+    );
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify_day(year: Year, solution: &Entry) -> [Status; 2] {
+    let known = ledger::known_answers(year, solution.day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        [None, None]
+    });
+
+    let actual = answers_from_records(&solution.run(year, false));
+
+    let [expected_1, expected_2] = known;
+    let [actual_1, actual_2] = actual;
+    [
+        Status::new(expected_1, actual_1),
+        Status::new(expected_2, actual_2),
+    ]
+}
+
+/// The answer or error of each part. If the input could not be parsed, both parts get its error.
+fn answers_from_records(records: &[PartRecord]) -> [Result<Option<String>, String>; 2] {
+    let answer = |part| {
+        let record = records
+            .iter()
+            .find(|r| r.part == part)
+            .or_else(|| records.iter().find(|r| r.part == PARSE));
+
+        match record.and_then(|r| r.error.clone()) {
+            Some(error) => Err(error),
+            None => Ok(record
+                .filter(|r| r.part == part)
+                .and_then(|r| r.answer.clone())),
+        }
+    };
+    [answer(1), answer(2)]
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers_from_records, Status};
+    use crate::day;
+    use crate::template::runner::{PartRecord, PartStatus, PARSE};
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(
+            Status::new(Some("1".into()), Ok(Some("1".into()))),
+            Status::Pass("1".into())
+        );
+        assert_eq!(
+            Status::new(Some("1".into()), Ok(None)),
+            Status::Fail {
+                expected: "1".into(),
+                actual: None
+            }
+        );
+        assert_eq!(
+            Status::new(None, Ok(Some("1".into()))),
+            Status::Missing(Some("1".into()))
+        );
+        assert_eq!(
+            Status::new(Some("1".into()), Err("panicked: oops".into())),
+            Status::Error {
+                expected: Some("1".into()),
+                error: "panicked: oops".into()
+            }
+        );
+    }
+
+    #[test]
+    fn reports_errors_of_failed_parts() {
+        let record = |part, answer: Option<&str>, error: Option<&str>| PartRecord {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            duration_nanos: 0,
+            samples: 1,
+            status: if error.is_some() {
+                PartStatus::Failed
+            } else {
+                PartStatus::Solved
+            },
+            error: error.map(Into::into),
+            stats: None,
+        };
+
+        assert_eq!(
+            answers_from_records(&[
+                record(PARSE, None, None),
+                record(1, Some("42"), None),
+                record(2, None, Some("panicked: oops")),
+            ]),
+            [Ok(Some("42".into())), Err("panicked: oops".into())]
+        );
+        assert_eq!(
+            answers_from_records(&[record(PARSE, None, Some("bad input"))]),
+            [Err("bad input".into()), Err("bad input".into())]
+        );
+        assert_eq!(answers_from_records(&[]), [Ok(None), Ok(None)]);
+    }
+}
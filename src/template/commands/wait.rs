@@ -0,0 +1,134 @@
+use std::{
+    io::{self, Write},
+    process,
+    time::Duration,
+};
+
+use crate::template::commands::{download, scaffold};
+use crate::template::release::{next_release, Clock, SystemClock};
+use crate::{Day, DaySet, Year};
+
+/// The time waited after a release before the puzzle is downloaded.
+const GRACE: Duration = Duration::from_secs(2);
+
+/// Counts down to the next puzzle of `year`, then downloads and scaffolds it.
+/// A failed download is reported, but the day is still scaffolded.
+pub fn handle(year: Year, options: &scaffold::Options) {
+    let day = wait(&SystemClock, year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let download = download::download_day(year, day);
+    if let Err(e) = &download {
+        eprintln!("failed to download day {day}: {e}");
+    }
+
+    scaffold::handle(year, &DaySet::from(day), options);
+
+    if download.is_err() {
+        process::exit(1);
+    }
+}
+
+/// Shows a countdown until the next puzzle of `year` is unlocked and returns its day.
+pub fn wait(clock: &impl Clock, year: Year) -> Result<Day, String> {
+    let (day, release) = next_release(year, clock.now())
+        .ok_or_else(|| format!("All puzzles of {year} are unlocked already."))?;
+    let unlock = release + GRACE;
+
+    let mut stdout = io::stdout();
+    while let Some(remaining) = unlock
+        .duration_since(clock.now())
+        .ok()
+        .filter(|d| !d.is_zero())
+    {
+        print!(
+            "\r⏳ Day {day} of {year} unlocks in {}",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} of {year} is unlocked!{}", " ".repeat(16));
+    Ok(day)
+}
+
+/// Formats a duration as `HH:MM:SS`, prefixed with the days if there are any.
+/// Seconds are rounded up, so the countdown ends at `00:00:01`.
+fn format_countdown(remaining: Duration) -> String {
+    let mut secs = remaining.as_secs();
+    if remaining.subsec_nanos() > 0 {
+        secs += 1;
+    }
+
+    let (days, hours, minutes, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{secs:02}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_countdown, wait, GRACE};
+    use crate::template::release::{release_time, Clock};
+    use crate::{day, year};
+    use std::{
+        cell::{Cell, RefCell},
+        time::{Duration, SystemTime},
+    };
+
+    /// A clock whose time only advances when it sleeps.
+    struct FakeClock {
+        now: Cell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn waits_for_next_release() {
+        let release = release_time(year!(2023), day!(12));
+        let clock = FakeClock {
+            now: Cell::new(release - Duration::from_millis(2500)),
+            sleeps: RefCell::new(vec![]),
+        };
+
+        assert_eq!(wait(&clock, year!(2023)), Ok(day!(12)));
+        assert_eq!(clock.now(), release + GRACE);
+        assert_eq!(clock.sleeps.borrow().len(), 5);
+    }
+
+    #[test]
+    fn fails_after_last_release() {
+        let clock = FakeClock {
+            now: Cell::new(release_time(year!(2023), day!(25))),
+            sleeps: RefCell::new(vec![]),
+        };
+
+        assert!(wait(&clock, year!(2023)).is_err());
+        assert!(clock.sleeps.borrow().is_empty());
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(500)), "00:00:01");
+        assert_eq!(format_countdown(Duration::from_secs(3725)), "01:02:05");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 59)),
+            "2d 00:00:59"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+/// Project settings, loaded from `aoc.toml` in the crate root.
+/// Every setting is optional, environment variables override the values of the file.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::template::root;
+use crate::Year;
+
+/// The config file that is used unless `AOC_CONFIG` points to another file.
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse config: {e}"),
+            Error::IO(e) => write!(f, "could not read config: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The main year, whose files live directly in `data_dir` and `bin_dir`.
+    /// Defaults to none, overridden by `AOC_YEAR`.
+    pub year: Option<Year>,
+    /// The root of the data directories. Defaults to `data`, overridden by `AOC_DATA_DIR`.
+    pub data_dir: PathBuf,
+    /// The directory of the solution binaries. Defaults to `src/bin`, overridden by `AOC_BIN_DIR`.
+    pub bin_dir: PathBuf,
+    /// The readme with the benchmark tables. Defaults to `README.md`, overridden by `AOC_README`.
+    pub readme: PathBuf,
+    /// The directory of the scaffold templates. Defaults to `templates`, overridden by `AOC_TEMPLATES_DIR`.
+    pub templates_dir: PathBuf,
+    pub benchmark: Benchmark,
+}
+
+/// How long each part is benchmarked by `--time`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Benchmark {
+    /// The time spent on the samples of one part, unless limited by the sample counts.
+    /// Defaults to 1000, overridden by `AOC_BENCH_MILLIS`.
+    pub target_millis: u64,
+    /// Defaults to 10.
+    pub min_samples: u64,
+    /// Defaults to 10000.
+    pub max_samples: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: PathBuf::from("data"),
+            bin_dir: PathBuf::from("src/bin"),
+            readme: PathBuf::from("README.md"),
+            templates_dir: PathBuf::from("templates"),
+            benchmark: Benchmark::default(),
+        }
+    }
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            target_millis: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl Benchmark {
+    pub fn target(&self) -> Duration {
+        Duration::from_millis(self.target_millis)
+    }
+}
+
+impl Config {
+    /// Parses a config file. Missing settings use their defaults.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        toml::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    /// Loads the config file at `path`, or the defaults if it does not exist.
+    /// Environment variables are applied on top of the file.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let mut config = match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        config.apply_env(|key| env::var(key).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Rejects settings that cannot be used, like more minimum than maximum samples.
+    fn validate(&self) -> Result<(), Error> {
+        let benchmark = &self.benchmark;
+        if benchmark.target_millis == 0 {
+            return Err(Error::Parser(
+                "benchmark.target_millis has to be greater than 0.".into(),
+            ));
+        }
+        if benchmark.min_samples == 0 || benchmark.min_samples > benchmark.max_samples {
+            return Err(Error::Parser(format!(
+                "benchmark.min_samples ({}) has to be between 1 and benchmark.max_samples ({}).",
+                benchmark.min_samples, benchmark.max_samples
+            )));
+        }
+        Ok(())
+    }
+
+    /// Overrides settings with the variables returned by `var`.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), Error> {
+        if let Some(year) = var("AOC_YEAR") {
+            let year = year
+                .parse()
+                .map_err(|e| Error::Parser(format!("AOC_YEAR: {e}")))?;
+            self.year = Some(year);
+        }
+        if let Some(data_dir) = var("AOC_DATA_DIR") {
+            self.data_dir = data_dir.into();
+        }
+        if let Some(bin_dir) = var("AOC_BIN_DIR") {
+            self.bin_dir = bin_dir.into();
+        }
+        if let Some(readme) = var("AOC_README") {
+            self.readme = readme.into();
+        }
+        if let Some(templates_dir) = var("AOC_TEMPLATES_DIR") {
+            self.templates_dir = templates_dir.into();
+        }
+        if let Some(millis) = var("AOC_BENCH_MILLIS") {
+            self.benchmark.target_millis = millis
+                .parse()
+                .map_err(|e| Error::Parser(format!("AOC_BENCH_MILLIS: {e}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// The path of the config file: `AOC_CONFIG` if set, `aoc.toml` in the crate root otherwise.
+#[must_use]
+pub fn get_path() -> PathBuf {
+    root()
+        .join(env::var_os("AOC_CONFIG").map_or_else(|| PathBuf::from(DEFAULT_PATH), PathBuf::from))
+}
+
+lazy_static::lazy_static! {
+    static ref CONFIG: Config = Config::load(&get_path()).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+}
+
+/// The config of this project, loaded once on first use.
+/// Exits the process if the config file is invalid.
+pub fn get() -> &'static Config {
+    &CONFIG
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+    use crate::year;
+    use std::{env, fs, path::PathBuf};
+
+    #[test]
+    fn uses_defaults_for_missing_settings() {
+        let config = Config::parse("year = 2022\n[benchmark]\ntarget_millis = 200\n").unwrap();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(config.data_dir, PathBuf::from("data"));
+        assert_eq!(config.benchmark.target_millis, 200);
+        assert_eq!(config.benchmark.max_samples, 10000);
+        assert!(Config::parse("unknown = 1").is_err());
+        assert!(Config::parse("year = 1999").is_err());
+    }
+
+    #[test]
+    fn overrides_settings_from_env() {
+        let mut config = Config::parse("year = 2022\ndata_dir = \"puzzles\"").unwrap();
+        config
+            .apply_env(|key| match key {
+                "AOC_YEAR" => Some("2024".into()),
+                "AOC_README" => Some("docs/README.md".into()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.year, Some(year!(2024)));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.readme, PathBuf::from("docs/README.md"));
+    }
+
+    #[test]
+    fn rejects_invalid_benchmark_settings() {
+        let path = env::temp_dir().join(format!("aoc-invalid-config-{}.toml", std::process::id()));
+
+        for benchmark in [
+            "min_samples = 100\nmax_samples = 10",
+            "target_millis = 0",
+            "min_samples = 0",
+        ] {
+            fs::write(&path, format!("[benchmark]\n{benchmark}\n")).unwrap();
+            let error = Config::load(&path).unwrap_err();
+            assert!(error.to_string().contains("benchmark."), "{error}");
+        }
+
+        fs::write(&path, "[benchmark]\nmin_samples = 5\nmax_samples = 5\n").unwrap();
+        assert!(Config::load(&path).is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+/// Module that extracts example inputs and their expected answers from puzzle descriptions.
+/// The expected answers are recorded in `examples/NN.json` of the data directory of a year.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::registry::Entry;
+use crate::template::{config, data_dir, input, root};
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse example manifest: {e}"),
+            Error::IO(e) => write!(f, "could not access example manifest: {e}"),
+        }
+    }
+}
+
+/// An example file and the answers the puzzle description gives for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Example {
+    /// The name of the file in `examples` without extension, e.g. `01`.
+    pub name: String,
+    #[serde(default)]
+    pub part_1: Option<String>,
+    #[serde(default)]
+    pub part_2: Option<String>,
+}
+
+/// The examples of one day, stored in `examples/NN.json` of the data directory of its year.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub examples: Vec<Example>,
+}
+
+/// A code block of a puzzle description and the part whose description contains it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub part: u8,
+    pub content: String,
+}
+
+#[must_use]
+pub fn get_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("examples").join(format!("{day}.json"))
+}
+
+impl Manifest {
+    /// Loads the manifest of a day, or an empty one if none was saved yet.
+    pub fn load(year: Year, day: Day) -> Result<Self, Error> {
+        Self::load_from(&get_path(year, day))
+    }
+
+    /// Loads the manifest at `path`, or an empty one if the file does not exist.
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s).map_err(|e| Error::Parser(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, year: Year, day: Day) -> Result<(), Error> {
+        self.save_to(&get_path(year, day))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| Error::Parser(e.to_string()))?;
+        fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Adds an example, or replaces the example with the same name.
+    pub fn insert(&mut self, example: Example) {
+        match self.examples.iter_mut().find(|e| e.name == example.name) {
+            Some(existing) => *existing = example,
+            None => self.examples.push(example),
+        }
+    }
+}
+
+/// Runs a solution against the example `name` of `year` (`None` for the main year)
+/// and asserts that `part` is solved with the `expected` answer.
+/// Called by the tests that `example_tests!` generates from the manifest.
+pub fn check(entry: &Entry, year: Option<Year>, name: &str, part: u8, expected: &str) {
+    let dir = year.map_or_else(|| root().join(&config::get().data_dir), data_dir);
+    let input = input::read_path(&dir.join("examples").join(format!("{name}.txt")), None)
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    let records = entry.solve(&input);
+    let record = records
+        .iter()
+        .find(|record| record.part == part)
+        .unwrap_or_else(|| panic!("day {} did not run part {part}", entry.day));
+
+    if let Some(error) = &record.error {
+        panic!("part {part} of example {name} failed: {error}");
+    }
+    assert_eq!(
+        record.answer.as_deref(),
+        Some(expected),
+        "part {part} of example {name}"
+    );
+}
+
+/// Finds the fenced code blocks of a puzzle description.
+pub fn code_blocks(puzzle: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in puzzle.lines() {
+        match current.as_mut() {
+            Some(lines) if line.trim_end() == "```" => {
+                let mut content = lines.join("\n");
+                content.push('\n');
+                blocks.push(CodeBlock { part, content });
+                current = None;
+            }
+            Some(lines) => lines.push(line),
+            None if line.trim_start().starts_with("```") => current = Some(vec![]),
+            None if line.contains("--- Part Two ---") => part = 2,
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Finds the expected answer of each part: the last emphasized code span (`` `*42*` ``)
+/// in the description of the part, outside of code blocks.
+pub fn expected_answers(puzzle: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part = 1;
+    let mut is_code = false;
+
+    for line in puzzle.lines() {
+        if line.trim_start().starts_with("```") {
+            is_code = !is_code;
+            continue;
+        }
+        if is_code {
+            continue;
+        }
+        if line.contains("--- Part Two ---") {
+            part = 2;
+        }
+
+        for span in line.split('`').skip(1).step_by(2) {
+            if let Some(answer) = span.strip_prefix('*').and_then(|s| s.strip_suffix('*')) {
+                if !answer.is_empty() {
+                    answers[part - 1] = Some(answer.to_string());
+                }
+            }
+        }
+    }
+
+    answers
+}
+
+/// The answers that belong to an example block. Part 2 reuses the example of part 1
+/// unless its description has its own code blocks.
+pub fn answers_for_block(puzzle: &str, block: &CodeBlock) -> [Option<String>; 2] {
+    let [part_1, part_2] = expected_answers(puzzle);
+    let has_part_two_blocks = code_blocks(puzzle).iter().any(|b| b.part == 2);
+
+    match block.part {
+        1 if has_part_two_blocks => [part_1, None],
+        1 => [part_1, part_2],
+        _ => [None, part_2],
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers_for_block, code_blocks, expected_answers, Example, Manifest};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+treb7uchet
+```
+
+In this example, the values are `12` and `77`. Adding these together produces
+`*142*`.
+
+Your puzzle answer was `54634`.
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+`*not an answer*`
+```
+
+Adding these together produces `*281*`.";
+
+    #[test]
+    fn finds_code_blocks() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].part, 1);
+        assert_eq!(blocks[0].content, "1abc2\ntreb7uchet\n");
+        assert_eq!(blocks[1].part, 2);
+    }
+
+    #[test]
+    fn finds_expected_answers() {
+        assert_eq!(
+            expected_answers(PUZZLE),
+            [Some("142".into()), Some("281".into())]
+        );
+
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(
+            answers_for_block(PUZZLE, &blocks[0]),
+            [Some("142".into()), None]
+        );
+        assert_eq!(
+            answers_for_block(PUZZLE, &blocks[1]),
+            [None, Some("281".into())]
+        );
+
+        let part_one = PUZZLE.split("## --- Part Two").next().unwrap();
+        let blocks = code_blocks(part_one);
+        assert_eq!(
+            answers_for_block(part_one, &blocks[0]),
+            [Some("142".into()), None]
+        );
+    }
+
+    #[test]
+    fn replaces_examples_by_name() {
+        let mut manifest = Manifest::default();
+        manifest.insert(Example {
+            name: "01".into(),
+            part_1: Some("1".into()),
+            part_2: None,
+        });
+        manifest.insert(Example {
+            name: "01".into(),
+            part_1: Some("2".into()),
+            part_2: None,
+        });
+        assert_eq!(manifest.examples.len(), 1);
+        assert_eq!(manifest.examples[0].part_1.as_deref(), Some("2"));
+    }
+}
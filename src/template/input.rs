@@ -0,0 +1,293 @@
+/// Selects the input a solution is run against.
+use std::{
+    fmt::{Debug, Display},
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::template::{data_dir, relative};
+use crate::{Day, Year};
+
+#[cfg(feature = "embed_inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+}
+
+/// An input that could not be read.
+pub struct Error {
+    /// The file that was read, `None` for stdin.
+    pub path: Option<PathBuf>,
+    /// How to create a missing or empty file.
+    pub hint: Option<String>,
+    pub source: io::Error,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "could not read \"{}\": {}", path.display(), self.source)?,
+            None => write!(f, "could not read stdin: {}", self.source)?,
+        }
+        match &self.hint {
+            Some(hint) if self.is_missing() => write!(f, ". {hint}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error {
+    /// Whether the file does not exist or has no content.
+    pub fn is_missing(&self) -> bool {
+        matches!(
+            self.source.kind(),
+            io::ErrorKind::NotFound | io::ErrorKind::InvalidData
+        )
+    }
+}
+
+// shows the message when a test unwraps a missing example.
+impl Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Reads a file and converts CRLF line endings to LF. `hint` is suggested if the file does not exist.
+pub fn read_path(path: &Path, hint: Option<String>) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map(|input| normalize(input).0)
+        .map_err(|source| Error {
+            path: Some(relative(path).to_path_buf()),
+            hint,
+            source,
+        })
+}
+
+/// Converts CRLF line endings to LF, which solutions splitting on `\n` expect.
+/// Returns whether line endings were converted.
+fn normalize(input: String) -> (String, bool) {
+    if input.contains("\r\n") {
+        (input.replace("\r\n", "\n"), true)
+    } else {
+        (input, false)
+    }
+}
+
+/// Rejects inputs that are empty or only contain whitespace, like the files created by `scaffold`.
+fn check_not_empty(input: &str) -> io::Result<()> {
+    if input.trim().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the input is empty",
+        ));
+    }
+    Ok(())
+}
+
+/// The command that downloads the input of a day. The main year (`None`) needs no `--year`.
+#[must_use]
+pub fn download_command(year: Option<Year>, day: Day) -> String {
+    match year.filter(|year| !year.is_main()) {
+        Some(year) => format!("cargo download {day} --year {year}"),
+        None => format!("cargo download {day}"),
+    }
+}
+
+/// How to create the missing input of a day.
+#[must_use]
+pub fn download_hint(year: Option<Year>, day: Day) -> String {
+    format!("Run `{}` to download it.", download_command(year, day))
+}
+
+/// The puzzle input of a day that was embedded at compile time by the `embed_inputs` feature.
+#[allow(unused_variables)]
+pub fn embedded_input(year: Year, day: Day) -> Option<&'static str> {
+    #[cfg(feature = "embed_inputs")]
+    {
+        let year = (!year.is_main()).then_some(year.into_inner());
+        embedded::INPUTS
+            .iter()
+            .find(|(y, d, _)| *y == year && *d == day.into_inner())
+            .map(|(_, _, input)| *input)
+    }
+    #[cfg(not(feature = "embed_inputs"))]
+    None
+}
+
+/// The input of a solution, selected by the arguments `--example [N]`, `--input <path>` or `-`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `inputs/NN.txt` of the data directory of the year.
+    #[default]
+    Puzzle,
+    /// An example in `examples/NN.txt`, or `examples/NN-N.txt` if a number is given.
+    Example(Option<u8>),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses the input source from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut sources = vec![];
+
+        if let Some(i) = args.iter().position(|x| x == "--input") {
+            let path = args.get(i + 1).ok_or("missing path after \"--input\".")?;
+            sources.push(InputSource::Path(path.into()));
+        }
+
+        if let Some(i) = args.iter().position(|x| x == "--example") {
+            let number = args.get(i + 1).and_then(|x| x.parse::<u8>().ok());
+            sources.push(InputSource::Example(number));
+        }
+
+        if args.iter().any(|x| x == "-") {
+            sources.push(InputSource::Stdin);
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("expecting only one of \"--example\", \"--input\" or \"-\".".into()),
+        }
+    }
+
+    /// The arguments that select this source when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            InputSource::Path(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["-".into()],
+        }
+    }
+
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// The file of this source. Files of the data directory are resolved against the crate root,
+    /// a path given with `--input` against the working directory.
+    pub fn path(&self, year: Year, day: Day) -> Option<PathBuf> {
+        let data = data_dir(year);
+        match self {
+            InputSource::Puzzle => Some(data.join("inputs").join(format!("{day}.txt"))),
+            InputSource::Example(None) => Some(data.join("examples").join(format!("{day}.txt"))),
+            InputSource::Example(Some(n)) => {
+                Some(data.join("examples").join(format!("{day}-{n}.txt")))
+            }
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input. Puzzle inputs embedded at compile time take precedence over files.
+    /// Empty inputs are rejected and CRLF line endings are converted with a warning,
+    /// whether the input is embedded or read at runtime.
+    pub fn read(&self, year: Year, day: Day) -> Result<String, Error> {
+        let embedded = embedded_input(year, day).filter(|_| self.is_puzzle());
+
+        let path = self.path(year, day);
+        let hint = match self {
+            InputSource::Puzzle => Some(download_hint(Some(year), day)),
+            InputSource::Example(_) => Some("Paste the example of the puzzle into it.".into()),
+            _ => None,
+        };
+        let error = |source| Error {
+            path: path.as_deref().map(|path| relative(path).to_path_buf()),
+            hint: hint.clone(),
+            source,
+        };
+
+        let input = match (embedded, &path) {
+            (Some(input), _) => Ok(input.to_string()),
+            (None, Some(path)) => fs::read_to_string(path),
+            (None, None) => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        }
+        .map_err(error)?;
+
+        check_not_empty(&input).map_err(error)?;
+
+        let (input, is_converted) = normalize(input);
+        if is_converted {
+            eprintln!("Warning: converted CRLF line endings of the input to LF.");
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_not_empty, normalize, read_path, InputSource};
+    use std::path::Path;
+
+    fn parse(args: &[&str]) -> Result<InputSource, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn parses_input_source() {
+        assert_eq!(parse(&["01", "--time"]), Ok(InputSource::Puzzle));
+        assert_eq!(parse(&["--example"]), Ok(InputSource::Example(None)));
+        assert_eq!(
+            parse(&["--example", "2", "--time"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["--input", "other.txt"]),
+            Ok(InputSource::Path("other.txt".into()))
+        );
+        assert_eq!(parse(&["-"]), Ok(InputSource::Stdin));
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--example", "-"]).is_err());
+    }
+
+    #[test]
+    fn suggests_download_for_missing_input() {
+        let error = read_path(
+            Path::new("missing/01.txt"),
+            Some("Run `cargo download 01` to download it.".into()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "could not read \"missing/01.txt\": {}. Run `cargo download 01` to download it.",
+                error.source
+            )
+        );
+
+        let error = read_path(Path::new("missing/01.txt"), None).unwrap_err();
+        assert!(!error.to_string().contains("cargo download"));
+    }
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize("1\r\n2\r\n".into()), ("1\n2\n".into(), true));
+        assert_eq!(normalize("1\n2\n".into()), ("1\n2\n".into(), false));
+        assert!(check_not_empty("").is_err());
+        assert!(check_not_empty(" \n\t\n").is_err());
+        assert!(check_not_empty("1\n").is_ok());
+    }
+
+    #[test]
+    fn round_trips_arguments() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+            InputSource::Path("other.txt".into()),
+            InputSource::Stdin,
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+        }
+    }
+}
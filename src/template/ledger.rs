@@ -0,0 +1,382 @@
+/// Module that keeps a local record of every submitted answer.
+/// The ledger is used to avoid submitting answers that are known to be wrong.
+use std::{cmp::Ordering, fmt::Display, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::{get_puzzle_path, SubmissionOutcome};
+use crate::template::data_dir;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answer ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access answer ledger: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartLedger {
+    /// The accepted answer for this part, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(default)]
+    pub submissions: Vec<Submission>,
+}
+
+/// All submissions made for one day, stored in `answers/NN.json` of the data directory of its year.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default)]
+    pub part_1: PartLedger,
+    #[serde(default)]
+    pub part_2: PartLedger,
+}
+
+/// The reason why an answer was not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    KnownCorrect,
+    KnownAnswer(String),
+    AlreadyRejected(SubmissionOutcome),
+    AboveBound(String),
+    BelowBound(String),
+}
+
+impl Rejection {
+    /// The exit code mirrors the outcome the server would have responded with.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Rejection::KnownCorrect => SubmissionOutcome::AlreadySolved.exit_code(),
+            Rejection::KnownAnswer(_) => SubmissionOutcome::Incorrect.exit_code(),
+            Rejection::AlreadyRejected(outcome) => outcome.exit_code(),
+            Rejection::AboveBound(_) => SubmissionOutcome::TooHigh.exit_code(),
+            Rejection::BelowBound(_) => SubmissionOutcome::TooLow.exit_code(),
+        }
+    }
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::KnownCorrect => write!(f, "this answer was already accepted."),
+            Rejection::KnownAnswer(answer) => {
+                write!(f, "this part was already solved with answer \"{answer}\".")
+            }
+            Rejection::AlreadyRejected(outcome) => {
+                write!(f, "this answer was already submitted: {outcome}")
+            }
+            Rejection::AboveBound(bound) => {
+                write!(
+                    f,
+                    "this answer is too high, \"{bound}\" was already too high."
+                )
+            }
+            Rejection::BelowBound(bound) => {
+                write!(
+                    f,
+                    "this answer is too low, \"{bound}\" was already too low."
+                )
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("answers").join(format!("{day}.json"))
+}
+
+impl Ledger {
+    /// Loads the ledger of a day. A missing file yields an empty ledger.
+    pub fn load(year: Year, day: Day) -> Result<Self, Error> {
+        match fs::read_to_string(get_path(year, day)) {
+            Ok(s) => serde_json::from_str(&s).map_err(|e| Error::Parser(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, year: Year, day: Day) -> Result<(), Error> {
+        let path = get_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let s = serde_json::to_string_pretty(self).map_err(|e| Error::Parser(e.to_string()))?;
+        fs::write(path, s + "\n")?;
+        Ok(())
+    }
+
+    pub fn part(&self, part: u8) -> &PartLedger {
+        match part {
+            1 => &self.part_1,
+            _ => &self.part_2,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartLedger {
+        match part {
+            1 => &mut self.part_1,
+            _ => &mut self.part_2,
+        }
+    }
+
+    /// Checks whether an answer is worth submitting given the previous submissions.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        let ledger = self.part(part);
+
+        if let Some(known) = &ledger.answer {
+            return Err(if known == answer {
+                Rejection::KnownCorrect
+            } else {
+                Rejection::KnownAnswer(known.clone())
+            });
+        }
+
+        if let Some(previous) = ledger.submissions.iter().find(|s| s.answer == answer) {
+            if previous.outcome != SubmissionOutcome::Correct {
+                return Err(Rejection::AlreadyRejected(previous.outcome.clone()));
+            }
+        }
+
+        if let Some(upper) = ledger.upper_bound() {
+            if matches!(
+                compare_numeric(answer, upper),
+                Some(Ordering::Greater | Ordering::Equal)
+            ) {
+                return Err(Rejection::AboveBound(upper.into()));
+            }
+        }
+
+        if let Some(lower) = ledger.lower_bound() {
+            if matches!(
+                compare_numeric(answer, lower),
+                Some(Ordering::Less | Ordering::Equal)
+            ) {
+                return Err(Rejection::BelowBound(lower.into()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the outcome of a submission. Rate-limited submissions are not recorded
+    /// because the server did not evaluate them.
+    pub fn record(&mut self, part: u8, answer: &str, outcome: &SubmissionOutcome) {
+        if matches!(outcome, SubmissionOutcome::RateLimited(_)) {
+            return;
+        }
+
+        let ledger = self.part_mut(part);
+
+        if *outcome == SubmissionOutcome::Correct {
+            ledger.answer = Some(answer.into());
+        }
+
+        ledger.submissions.push(Submission {
+            answer: answer.into(),
+            outcome: outcome.clone(),
+        });
+    }
+}
+
+impl PartLedger {
+    /// The smallest answer that was rejected as too high.
+    pub fn upper_bound(&self) -> Option<&str> {
+        self.bound(&SubmissionOutcome::TooHigh, Ordering::Less)
+    }
+
+    /// The largest answer that was rejected as too low.
+    pub fn lower_bound(&self) -> Option<&str> {
+        self.bound(&SubmissionOutcome::TooLow, Ordering::Greater)
+    }
+
+    fn bound(&self, outcome: &SubmissionOutcome, ordering: Ordering) -> Option<&str> {
+        self.submissions
+            .iter()
+            .filter(|s| s.outcome == *outcome)
+            .map(|s| s.answer.as_str())
+            .reduce(|bound, answer| {
+                if compare_numeric(answer, bound) == Some(ordering) {
+                    answer
+                } else {
+                    bound
+                }
+            })
+    }
+}
+
+/// Scrapes the "Your puzzle answer was `...`." lines of a puzzle description.
+/// The first answer belongs to part 1, the second one to part 2.
+pub fn answers_from_puzzle(puzzle: &str) -> [Option<String>; 2] {
+    let mut answers = puzzle.lines().filter_map(|line| {
+        line.trim()
+            .strip_prefix("Your puzzle answer was `")?
+            .split('`')
+            .next()
+            .map(str::to_string)
+    });
+
+    [answers.next(), answers.next()]
+}
+
+fn scraped_answers(year: Year, day: Day) -> [Option<String>; 2] {
+    fs::read_to_string(get_puzzle_path(year, day))
+        .map(|puzzle| answers_from_puzzle(&puzzle))
+        .unwrap_or_default()
+}
+
+/// Returns the known answers for both parts of a day.
+/// Answers in the ledger take precedence over answers scraped from `puzzles/NN.md`.
+pub fn known_answers(year: Year, day: Day) -> Result<[Option<String>; 2], Error> {
+    let ledger = Ledger::load(year, day)?;
+    let [scraped_1, scraped_2] = scraped_answers(year, day);
+    Ok([
+        ledger.part_1.answer.or(scraped_1),
+        ledger.part_2.answer.or(scraped_2),
+    ])
+}
+
+/// Persists answers found in `puzzles/NN.md` for parts without a known answer.
+pub fn import_from_puzzle(year: Year, day: Day) -> Result<(), Error> {
+    let mut ledger = Ledger::load(year, day)?;
+    let mut changed = false;
+
+    for (part, answer) in [1, 2].into_iter().zip(scraped_answers(year, day)) {
+        let ledger = ledger.part_mut(part);
+        if ledger.answer.is_none() && answer.is_some() {
+            ledger.answer = answer;
+            changed = true;
+        }
+    }
+
+    if changed {
+        ledger.save(year, day)?;
+    }
+
+    Ok(())
+}
+
+/// Compares two answers numerically. Returns [`None`] if either is not an integer.
+fn compare_numeric(a: &str, b: &str) -> Option<Ordering> {
+    let a = a.trim().parse::<i128>().ok()?;
+    let b = b.trim().parse::<i128>().ok()?;
+    Some(a.cmp(&b))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers_from_puzzle, Ledger, Rejection};
+    use crate::template::aoc_client::SubmissionOutcome;
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "100", &SubmissionOutcome::TooHigh);
+        ledger.record(1, "80", &SubmissionOutcome::TooHigh);
+        ledger.record(1, "20", &SubmissionOutcome::TooLow);
+        ledger.record(1, "50", &SubmissionOutcome::Incorrect);
+        ledger
+    }
+
+    #[test]
+    fn computes_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.part_1.upper_bound(), Some("80"));
+        assert_eq!(ledger.part_1.lower_bound(), Some("20"));
+        assert_eq!(ledger.part_2.upper_bound(), None);
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(1, "50"),
+            Err(Rejection::AlreadyRejected(SubmissionOutcome::Incorrect))
+        );
+        assert_eq!(
+            ledger.check(1, "90"),
+            Err(Rejection::AboveBound("80".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "80"),
+            Err(Rejection::AlreadyRejected(SubmissionOutcome::TooHigh))
+        );
+        assert_eq!(
+            ledger.check(1, "10"),
+            Err(Rejection::BelowBound("20".into()))
+        );
+        assert_eq!(ledger.check(1, "abc"), Ok(()));
+        assert_eq!(ledger.check(1, "42"), Ok(()));
+        assert_eq!(ledger.check(2, "90"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let mut ledger = get_mock_ledger();
+        ledger.record(1, "42", &SubmissionOutcome::Correct);
+        assert_eq!(ledger.part_1.answer.as_deref(), Some("42"));
+        assert_eq!(ledger.check(1, "42"), Err(Rejection::KnownCorrect));
+        assert_eq!(
+            ledger.check(1, "43"),
+            Err(Rejection::KnownAnswer("42".into()))
+        );
+    }
+
+    #[test]
+    fn ignores_rate_limited_submissions() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            2,
+            "42",
+            &SubmissionOutcome::RateLimited(std::time::Duration::from_secs(30)),
+        );
+        assert!(ledger.part_2.submissions.is_empty());
+        assert_eq!(ledger.check(2, "42"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let s = serde_json::to_string(&ledger).unwrap();
+        assert!(s.contains(r#"{"answer":"100","outcome":"too_high"}"#));
+        assert_eq!(serde_json::from_str::<Ledger>(&s).unwrap(), ledger);
+        assert_eq!(
+            serde_json::from_str::<Ledger>("{}").unwrap(),
+            Ledger::default()
+        );
+    }
+
+    #[test]
+    fn scrapes_answers_from_puzzle() {
+        let puzzle = "## --- Day 1: Trebuchet?! ---\n\nYour puzzle answer was `54634`.\n\n## --- Part Two ---\n\nYour puzzle answer was `53855`.\n";
+        assert_eq!(
+            answers_from_puzzle(puzzle),
+            [Some("54634".into()), Some("53855".into())]
+        );
+        assert_eq!(
+            answers_from_puzzle("Your puzzle answer was `54634`."),
+            [Some("54634".into()), None]
+        );
+        assert_eq!(answers_from_puzzle("## --- Day 1 ---"), [None, None]);
+    }
+}
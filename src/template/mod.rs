@@ -1,46 +1,269 @@
-use crate::Day;
-use std::{env, fs};
+use crate::year::FIRST_YEAR;
+use crate::{Day, Year};
+use std::env;
+use std::path::{Path, PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod baseline;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input;
+pub mod ledger;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod release;
 pub mod runner;
+pub mod solution;
+pub mod stats;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// The root of the crate, which the paths of `aoc.toml` are relative to.
+/// Defaults to the directory of `Cargo.toml`, set `AOC_ROOT` to override it.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn root() -> PathBuf {
+    env::var_os("AOC_ROOT").map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from)
 }
 
-pub fn read_filepath(folder: &str, filename: &str) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+/// Strips the crate root from a path, for messages.
+#[must_use]
+pub fn relative(path: &Path) -> &Path {
+    path.strip_prefix(root()).unwrap_or(path)
+}
+
+/// The data directory of a year: `data` for the main year, `data/YYYY` for other years.
+/// The root is configured by `data_dir` in `aoc.toml`.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    let data = root().join(&config::get().data_dir);
+    if year.is_main() {
+        data
+    } else {
+        data.join(year.to_string())
+    }
+}
+
+/// The name of the solution binary of a day: `NN` for the main year, `YYYY-NN` for other years.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    if year.is_main() {
+        day.to_string()
+    } else {
+        format!("{year}-{day}")
+    }
+}
+
+/// Splits the name of a solution binary into its year and day, the inverse of [`bin_name`]:
+/// `NN` for the main year (`None`), `YYYY-NN` for other years.
+/// Mirrors `parse_bin_name` of the build script.
+pub fn parse_bin_name(name: &str) -> Option<(Option<Year>, Day)> {
+    let is_number = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
+    match name.split_once('-') {
+        Some((year, day)) if is_number(year, 4) && is_number(day, 2) => {
+            Some((Some(year.parse().ok()?), day.parse().ok()?))
+        }
+        Some(_) => None,
+        None => is_number(name, 2)
+            .then(|| name.parse().ok())
+            .flatten()
+            .map(|day| (None, day)),
+    }
+}
+
+/// The year of a solution binary at compile time, which `solution!` checks its day against:
+/// the year in the name of `YYYY-NN` binaries, the main year of `aoc.toml` for `NN` binaries.
+/// Without a main year, `NN` binaries get the first year, which allows the days of the longest events.
+// Not part of the public API
+#[doc(hidden)]
+pub const fn __bin_year(bin_name: &str) -> u16 {
+    let name = bin_name.as_bytes();
+    if name.len() == 7 && name[4] == b'-' {
+        if let Some(year) = parse_digits(name, 4) {
+            return year;
+        }
+    }
+
+    match option_env!("AOC_MAIN_YEAR") {
+        Some(year) => match parse_digits(year.as_bytes(), year.len()) {
+            Some(year) => year,
+            None => FIRST_YEAR,
+        },
+        None => FIRST_YEAR,
+    }
+}
+
+/// Parses the first `len` bytes of `bytes` as a decimal number.
+const fn parse_digits(bytes: &[u8], len: usize) -> Option<u16> {
+    if len == 0 || len > 4 || bytes.len() < len {
+        return None;
+    }
+
+    let mut value = 0;
+    let mut i = 0;
+    while i < len {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        value = value * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+    Some(value)
+}
+
+/// The source file of the solution of a day in the `bin_dir` configured in `aoc.toml`.
+#[must_use]
+pub fn bin_path(year: Year, day: Day) -> PathBuf {
+    config::get()
+        .bin_dir
+        .join(format!("{}.rs", bin_name(year, day)))
+}
+
+/// The last day of `year` that has a solution.
+pub fn latest_scaffolded_day(year: Year) -> Option<Day> {
+    year.days()
+        .filter(|day| root().join(bin_path(year, *day)).exists())
+        .last()
+}
+
+/// Helper function that reads a text file of the main year to a string.
+pub fn read_file(folder: &str, day: Day) -> Result<String, input::Error> {
+    let path = root()
+        .join(&config::get().data_dir)
+        .join(folder)
+        .join(format!("{day}.txt"));
+    let hint = (folder == "inputs").then(|| input::download_hint(None, day));
+    input::read_path(&path, hint)
+}
+
+/// Helper function that reads a text file of any year to a string.
+pub fn read_file_for_year(year: Year, folder: &str, day: Day) -> Result<String, input::Error> {
+    let path = data_dir(year).join(folder).join(format!("{day}.txt"));
+    let hint = (folder == "inputs").then(|| input::download_hint(Some(year), day));
+    input::read_path(&path, hint)
+}
+
+pub fn read_filepath(folder: &str, filename: &str) -> Result<String, input::Error> {
+    let path = root()
+        .join(&config::get().data_dir)
         .join(folder)
         .join(format!("{filename}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::read_path(&path, None)
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also registers both parts so the main binary can run the day in-process.
+///
+/// Pass a parse function as second argument to parse the input once and share the result
+/// with both parts. The parts then take a reference to the parsed input and the runner
+/// reports the parse time separately.
+///
+/// Use `solution!(DAY => Type)` to register an implementor of the
+/// [`Solution`](crate::Solution) trait instead of free functions.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
         /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        const DAY: advent_of_code::Day = advent_of_code::day!(
+            advent_of_code::template::__bin_year(env!("CARGO_BIN_NAME")),
+            $day
+        );
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let year = bin_year(env!("CARGO_BIN_NAME"));
+            let input = read_input(year, DAY);
+            run_part(part_one, &input, year, DAY, 1);
+            run_part(part_two, &input, year, DAY, 2);
+        }
+
+        // Not part of the public API
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __register() -> advent_of_code::template::registry::Entry {
+            advent_of_code::template::registry::Entry::new(DAY, part_one, part_two)
+        }
+    };
+    ($day:expr, $parse:path) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!(
+            advent_of_code::template::__bin_year(env!("CARGO_BIN_NAME")),
+            $day
+        );
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let year = bin_year(env!("CARGO_BIN_NAME"));
+            let input = read_input(year, DAY);
+            let parsed = run_parse($parse, input.as_str(), DAY);
+            run_part(|parsed| part_one(parsed), &parsed, year, DAY, 1);
+            run_part(|parsed| part_two(parsed), &parsed, year, DAY, 2);
+        }
+
+        // Not part of the public API
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __register() -> advent_of_code::template::registry::Entry {
+            use advent_of_code::template::runner::*;
+            advent_of_code::template::registry::Entry::from_fn(
+                DAY,
+                |input: &str, is_timed: bool| {
+                    let (parsed, record) = solve_parse($parse, input, DAY, is_timed, |_| {});
+                    let Some(parsed) = parsed else {
+                        return vec![record];
+                    };
+                    vec![
+                        record,
+                        solve_part(|parsed| part_one(parsed), &parsed, DAY, 1, is_timed, |_| {}),
+                        solve_part(|parsed| part_two(parsed), &parsed, DAY, 2, is_timed, |_| {}),
+                    ]
+                },
+            )
+        }
+    };
+    ($day:expr => $solution:ty) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!(
+            advent_of_code::template::__bin_year(env!("CARGO_BIN_NAME")),
+            $day
+        );
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let year = bin_year(env!("CARGO_BIN_NAME"));
+            let input = read_input(year, DAY);
+            run_solution::<$solution>(&input, year, DAY);
+        }
+
+        // Not part of the public API
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __register() -> advent_of_code::template::registry::Entry {
+            advent_of_code::template::registry::Entry::from_solution::<$solution>(DAY)
+        }
+    };
+}
+
+/// Generates a test for each example and part with an expected answer in the manifest
+/// `examples/NN.json` of the day. Each test runs the solution registered by `solution!`
+/// against the example file and compares the answer.
+///
+/// The build script turns the manifests into tests, so they stay in sync with the data.
+/// Record examples with `cargo examples DAY` or add them to the manifest by hand.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/examples/",
+                env!("CARGO_BIN_NAME"),
+                ".rs"
+            ));
         }
     };
 }
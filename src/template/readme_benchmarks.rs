@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::runner::{PartRecord, PARSE};
+use crate::template::stats::Statistic;
+use crate::template::{bin_path, config, root};
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,23 +24,68 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Collects the timings of a day from the records emitted by its solution.
+    /// Benchmarked parts are summarized by `statistic`.
+    pub fn from_records(day: Day, records: &[PartRecord], statistic: Statistic) -> Self {
+        let mut timings = Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        for record in records
+            .iter()
+            .filter(|r| r.is_parse() || r.answer.is_some())
+        {
+            let duration = record.duration_by(statistic);
+            let timing = Some(format!("{duration:.1?}"));
+            match record.part {
+                PARSE => timings.parse = timing,
+                1 => timings.part_1 = timing,
+                _ => timings.part_2 = timing,
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = duration.as_nanos() as f64;
+            timings.total_nanos += nanos;
+        }
+
+        timings
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// The link to the solution of a day. Solutions of the main year (`None`) have no year in their name.
+fn link_to_bin(year: Option<Year>, day: Day) -> String {
+    let path = match year {
+        Some(year) => bin_path(year, day),
+        None => config::get().bin_dir.join(format!("{day}.rs")),
+    };
+    format!("./{}", path.display())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// The marker around the table of a year. The table of the main year (`None`) has no year in its marker.
+fn marker(year: Option<Year>) -> String {
+    match year {
+        None => MARKER.into(),
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+    }
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,23 +106,33 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    year: Option<Year>,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> String {
+    let marker = marker(year);
+    let header = match year {
+        None => format!("{prefix} Benchmarks"),
+        Some(year) => format!("{prefix} Benchmarks {year}"),
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = link_to_bin(year, timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -82,47 +140,65 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Replaces the table of a year. Tables of other years than the main year (`None`)
+/// are appended to the readme if they do not exist yet.
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = marker(year);
+    if year.is_some() && !s.contains(&marker) {
+        s.push_str(&format!("\n{marker}\n{marker}\n"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+    let path = root().join(&config::get().readme);
+    let year = (!year.is_main()).then_some(year);
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
+    update_content(&mut readme, year, timings, total_millis)?;
+    fs::write(&path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::template::runner::{PartRecord, PartStatus, PARSE};
+    use crate::template::stats::Statistic;
+    use crate::{day, year};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -134,28 +210,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,18 +239,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, None, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +259,49 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn appends_table_of_other_year() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, Some(year!(2022)), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Some(year!(2022)), get_mock_timings(), 190.0).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2022 --->").count(), 2);
+        assert_eq!(s.matches("## Benchmarks 2022").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2022-01.rs) |"));
+    }
+
+    #[test]
+    fn collects_timings_from_records() {
+        let record = |part, answer: Option<&str>, duration_nanos| PartRecord {
+            day: day!(1),
+            part,
+            answer: answer.map(str::to_string),
+            duration_nanos,
+            samples: 10,
+            status: if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            error: None,
+            stats: None,
+        };
+
+        let timings = Timings::from_records(
+            day!(1),
+            &[
+                record(PARSE, None, 2_000),
+                record(1, Some("42"), 74_130),
+                record(2, None, 1_000),
+            ],
+            Statistic::Median,
+        );
+
+        assert_eq!(timings.parse.as_deref(), Some("2.0µs"));
+        assert_eq!(timings.part_1.as_deref(), Some("74.1µs"));
+        assert_eq!(timings.part_2, None);
+        assert_eq!(timings.total_nanos, 76_130_f64);
+    }
 }
@@ -0,0 +1,91 @@
+/// In-process access to the solutions in `src/bin`.
+/// The build script includes every solution as a module of the main binary and collects
+/// the [`Entry`] each of them registers via the `solution!` macro.
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::template::input::InputSource;
+use crate::template::runner::{solve_part, solve_solution, PartRecord, PartResult};
+use crate::template::solution::Solution;
+use crate::{Day, Year};
+
+type Run = Box<dyn Fn(&str, bool) -> Vec<PartRecord> + Send + Sync>;
+
+/// A type-erased solution of one day.
+pub struct Entry {
+    pub day: Day,
+    run: Run,
+}
+
+impl Entry {
+    pub fn new<R1: PartResult, R2: PartResult>(
+        day: Day,
+        part_one: impl Fn(&str) -> R1 + Send + Sync + 'static,
+        part_two: impl Fn(&str) -> R2 + Send + Sync + 'static,
+    ) -> Self {
+        Self::from_fn(day, move |input, is_timed| {
+            vec![
+                solve_part(&part_one, input, day, 1, is_timed, |_| {}),
+                solve_part(&part_two, input, day, 2, is_timed, |_| {}),
+            ]
+        })
+    }
+
+    /// Creates a solution from a function that runs all steps of the day against an input.
+    /// Used by the `solution!` macro for days with a parse step.
+    pub fn from_fn(
+        day: Day,
+        run: impl Fn(&str, bool) -> Vec<PartRecord> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            day,
+            run: Box::new(run),
+        }
+    }
+
+    /// Creates an entry for an implementor of the [`Solution`] trait.
+    pub fn from_solution<S: Solution + 'static>(day: Day) -> Self {
+        Self::from_fn(day, move |input, is_timed| {
+            solve_solution::<S>(input, day, is_timed)
+        })
+    }
+
+    /// Runs both parts against the input of the day in `year`.
+    /// Returns no records if the input could not be read or the solution panicked.
+    pub fn run(&self, year: Year, is_timed: bool) -> Vec<PartRecord> {
+        let input = match InputSource::Puzzle.read(year, self.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{e}");
+                return vec![];
+            }
+        };
+
+        panic::catch_unwind(AssertUnwindSafe(|| (self.run)(&input, is_timed))).unwrap_or_default()
+    }
+
+    /// Runs all steps of the day against `input` once, without timing.
+    pub fn solve(&self, input: &str) -> Vec<PartRecord> {
+        (self.run)(input, false)
+    }
+}
+
+/// A solution found by the build script: the year in the name of its binary
+/// (`None` for the main year) and the function that registers it.
+pub type Candidate = (Option<u16>, fn() -> Entry);
+
+/// Registers the solutions of `year`.
+pub fn select(year: Year, candidates: &[Candidate]) -> Vec<Entry> {
+    candidates
+        .iter()
+        .filter(|(bin_year, _)| match bin_year {
+            None => year.is_main(),
+            Some(bin_year) => year == *bin_year,
+        })
+        .map(|(_, register)| register())
+        .collect()
+}
+
+/// Finds the solution registered for a day.
+pub fn find(solutions: &[Entry], day: Day) -> Option<&Entry> {
+    solutions.iter().find(|solution| solution.day == day)
+}
@@ -0,0 +1,150 @@
+/// Release times of the puzzles, which unlock at midnight US Eastern time.
+/// Time is read from a [`Clock`], so code that waits for a puzzle can be tested offline.
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Day, Year};
+
+/// US Eastern time is UTC-5 in December, daylight saving time is not in effect.
+const EASTERN_OFFSET: Duration = Duration::from_secs(5 * 60 * 60);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A source of the current time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the operating system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// When the puzzle of a day unlocks: at 05:00 UTC on that day of December.
+pub fn release_time(year: Year, day: Day) -> SystemTime {
+    let days = days_from_civil(year.into_inner().into(), 12, day.into_inner().into());
+    #[allow(clippy::cast_sign_loss)]
+    let days = days as u64;
+    UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY) + EASTERN_OFFSET
+}
+
+/// The next puzzle of `year` that unlocks after `now`, or `None` if all of them are unlocked.
+pub fn next_release(year: Year, now: SystemTime) -> Option<(Day, SystemTime)> {
+    year.days()
+        .map(|day| (day, release_time(year, day)))
+        .find(|(_, time)| *time > now)
+}
+
+/// Today's puzzle during December of `year`: the day of the month of `now` in US Eastern time,
+/// if the event has a puzzle on that day.
+pub fn puzzle_day(year: Year, now: SystemTime) -> Option<Day> {
+    let eastern = now
+        .checked_sub(EASTERN_OFFSET)?
+        .duration_since(UNIX_EPOCH)
+        .ok()?;
+    #[allow(clippy::cast_possible_wrap)]
+    let (civil_year, month, day) = civil_from_days((eastern.as_secs() / SECONDS_PER_DAY) as i64);
+
+    let day = Day::new(u8::try_from(day).ok()?)?;
+    (civil_year == i64::from(year.into_inner()) && month == 12 && year.has_day(day)).then_some(day)
+}
+
+/// The number of days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of a number of days since 1970-01-01, the inverse of [`days_from_civil`].
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{civil_from_days, days_from_civil, next_release, puzzle_day, release_time};
+    use crate::{day, year};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn counts_days_since_epoch() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+        assert_eq!(civil_from_days(19_692), (2023, 12, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn releases_at_midnight_eastern() {
+        // 2023-12-01T05:00:00Z
+        let release = UNIX_EPOCH + Duration::from_secs(1_701_406_800);
+        assert_eq!(release_time(year!(2023), day!(1)), release);
+
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            next_release(year!(2023), release - second),
+            Some((day!(1), release))
+        );
+        assert_eq!(
+            next_release(year!(2023), release).map(|(day, _)| day),
+            Some(day!(2))
+        );
+        assert_eq!(
+            next_release(year!(2023), release_time(year!(2023), day!(25))),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_todays_puzzle() {
+        let release = release_time(year!(2023), day!(12));
+        let second = Duration::from_secs(1);
+
+        assert_eq!(puzzle_day(year!(2023), release), Some(day!(12)));
+        assert_eq!(puzzle_day(year!(2023), release - second), Some(day!(11)));
+        assert_eq!(puzzle_day(year!(2025), release), None);
+
+        let release = release_time(year!(2025), day!(12));
+        assert_eq!(puzzle_day(year!(2025), release), Some(day!(12)));
+        assert_eq!(
+            puzzle_day(year!(2025), release + 24 * 60 * 60 * second),
+            None
+        );
+        assert_eq!(
+            puzzle_day(year!(2023), release_time(year!(2023), day!(1)) - second),
+            None
+        );
+    }
+}
@@ -1,63 +1,473 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::commands::download;
+use crate::template::input::{self, InputSource};
+use crate::template::solution::Solution;
+use crate::template::stats::{BenchStats, Statistic};
+use crate::template::{
+    aoc_client, config, ledger::Ledger, parse_bin_name, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Day, DaySet, Year};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The output format of a solution binary, selected via `--format <human|json>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+}
+
+impl Format {
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let format = args
+            .iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1));
+
+        match format.map(String::as_str) {
+            None | Some("human") => Format::Human,
+            Some("json") => Format::Json,
+            Some(x) => {
+                eprintln!("Unknown format: {x}. Expecting \"human\" or \"json\".");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part returned an error or panicked.
+    Failed,
+}
+
+/// The return type of a solution part.
+/// Parts return `Option<T>`, with [`None`] for unsolved parts, or `Result<T, E>` to report errors.
+pub trait PartResult {
+    /// The answer of the part, `Ok(None)` if it is not solved yet.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// The part number of the records emitted for the shared parse step of a solution.
+pub const PARSE: u8 = 0;
+
+/// The result of running one part of a solution.
+/// With `--format json`, every part is printed to stdout as one JSON object per line.
+/// Solutions with a parse step also emit a record with part [`PARSE`] for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The median of a benchmark or the duration of a single run.
+    pub duration_nanos: u64,
+    pub samples: u64,
+    pub status: PartStatus,
+    /// The error message of a failed part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<BenchStats>,
+}
+
+impl PartRecord {
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.duration_nanos)
+    }
+
+    /// The duration summarized by `statistic` if the part was benchmarked.
+    pub fn duration_by(&self, statistic: Statistic) -> Duration {
+        self.stats
+            .as_ref()
+            .map_or_else(|| self.duration(), |stats| stats.get(statistic))
+    }
+
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE
+    }
+
+    /// Parses a line of solution output. Returns [`None`] for lines that are not a record.
+    pub fn from_line(line: &str) -> Option<Self> {
+        serde_json::from_str(line.trim()).ok()
+    }
+}
+
+/// Reads the input selected by the arguments of the solution binary, the puzzle input by default.
+pub fn read_input(year: Year, day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}");
+        process::exit(1);
+    });
+
+    let exit = |e: input::Error| -> ! {
+        eprintln!("Failed to read input: {e}");
+        process::exit(1);
+    };
+
+    match source.read(year, day) {
+        Ok(input) => input,
+        Err(e) if source.is_puzzle() && e.is_missing() => {
+            eprintln!("Failed to read input: {e}");
+            if !offer_download(year, day) {
+                process::exit(1);
+            }
+            source.read(year, day).unwrap_or_else(|e| exit(e))
+        }
+        Err(e) => exit(e),
+    }
+}
+
+/// Asks whether to download a missing puzzle input. Only asks when run in a terminal.
+fn offer_download(year: Year, day: Day) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+
+    eprint!("Download the input of day {day} now? [y/N] ");
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() || !answer.trim().eq_ignore_ascii_case("y") {
+        return false;
+    }
+
+    download::handle(year, &DaySet::from(day));
+    true
+}
+
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
+    let format = Format::from_args();
+    let is_timed = is_timed();
+
+    let record = solve_part(func, input, day, part, is_timed, |result| {
+        if format == Format::Human {
+            print_result(&result.to_answer().ok().flatten(), &part_str, "");
+            if is_timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
+
+    match format {
+        Format::Human => print_record(&record),
+        Format::Json => println!("{}", serde_json::to_string(&record).unwrap()),
+    }
+
+    if let Some(result) = record.answer {
+        match submit_result(result, year, day, part) {
+            Some(Ok(outcome)) => {
+                println!("{outcome}");
+                if outcome.exit_code() != 0 {
+                    process::exit(outcome.exit_code());
+                }
+            }
+            Some(Err(e)) => {
+                eprintln!("failed to submit result: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Parses the input and runs both parts of a [`Solution`].
+pub fn run_solution<S: Solution>(input: &str, year: Year, day: Day) {
+    match run_parse(S::parse, input, day) {
+        Ok(parsed) => {
+            run_part(S::part_one, &parsed, year, day, 1);
+            run_part(S::part_two, &parsed, year, day, 2);
+        }
+        Err(e) => {
+            eprintln!("Failed to parse the input of day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Parses the input and runs both parts of a [`Solution`] without printing.
+/// If the input could not be parsed, only the failed parse record is returned.
+pub fn solve_solution<S: Solution>(input: &str, day: Day, is_timed: bool) -> Vec<PartRecord> {
+    let (parsed, mut record) = solve_parse(S::parse, input, day, is_timed, |_| {});
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match parsed {
+        Some(Ok(parsed)) => vec![
+            record,
+            solve_part(S::part_one, &parsed, day, 1, is_timed, |_| {}),
+            solve_part(S::part_two, &parsed, day, 2, is_timed, |_| {}),
+        ],
+        Some(Err(e)) => {
+            record.status = PartStatus::Failed;
+            record.error = Some(e.to_string());
+            vec![record]
+        }
+        None => vec![record],
     }
 }
 
+/// Parses the input once and returns the result, which is shared by both parts.
+/// Exits if the parser panics.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I, day: Day) -> P {
+    let format = Format::from_args();
+    let is_timed = is_timed();
+
+    let (parsed, record) = solve_parse(func, input, day, is_timed, |_| {
+        if format == Format::Human && is_timed {
+            print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    });
+
+    match format {
+        Format::Human => print_record(&record),
+        Format::Json => println!("{}", serde_json::to_string(&record).unwrap()),
+    }
+
+    parsed.unwrap_or_else(|| process::exit(1))
+}
+
+/// Runs the parse step of a solution without printing and collects its [`PartRecord`].
+/// A panic is caught and reported as a failed parse step, in which case no input is returned.
+pub fn solve_parse<I: Clone, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    day: Day,
+    is_timed: bool,
+    hook: impl Fn(&P),
+) -> (Option<P>, PartRecord) {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_timed(func, input, is_timed, hook)));
+
+    let (parsed, measurement, error) = match outcome {
+        Ok((parsed, measurement)) => (Some(parsed), measurement, None),
+        Err(payload) => (
+            None,
+            Measurement {
+                duration: Duration::ZERO,
+                samples: 0,
+                stats: None,
+            },
+            Some(panic_message(payload.as_ref())),
+        ),
+    };
+
+    #[allow(clippy::cast_possible_truncation)]
+    let record = PartRecord {
+        day,
+        part: PARSE,
+        answer: None,
+        duration_nanos: measurement.duration.as_nanos() as u64,
+        samples: measurement.samples as u64,
+        status: if error.is_some() {
+            PartStatus::Failed
+        } else {
+            PartStatus::Solved
+        },
+        error,
+        stats: measurement.stats,
+    };
+
+    (parsed, record)
+}
+
+/// Runs one part of a solution without printing and collects its [`PartRecord`].
+/// The hook is called with the result of the first execution, before benchmarking starts.
+/// A panic is caught and reported as a failed part, so it does not abort the other part.
+pub fn solve_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+    hook: impl Fn(&R),
+) -> PartRecord {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_timed(func, input, is_timed, hook)));
+
+    let (answer, measurement) = match outcome {
+        Ok((result, measurement)) => (result.to_answer(), measurement),
+        Err(payload) => (
+            Err(panic_message(payload.as_ref())),
+            Measurement {
+                duration: Duration::ZERO,
+                samples: 0,
+                stats: None,
+            },
+        ),
+    };
+
+    let (answer, status, error) = match answer {
+        Ok(Some(answer)) => (Some(answer), PartStatus::Solved, None),
+        Ok(None) => (None, PartStatus::Unsolved, None),
+        Err(e) => (None, PartStatus::Failed, Some(e)),
+    };
+
+    #[allow(clippy::cast_possible_truncation)]
+    PartRecord {
+        day,
+        part,
+        answer,
+        duration_nanos: measurement.duration.as_nanos() as u64,
+        samples: measurement.samples as u64,
+        status,
+        error,
+        stats: measurement.stats,
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".into());
+
+    format!("panicked: {message}")
+}
+
+/// Prints a part in the human-readable format.
+pub fn print_record(record: &PartRecord) {
+    if let Some(error) = &record.error {
+        let name = if record.is_parse() {
+            "Parse".to_string()
+        } else {
+            format!("Part {}", record.part)
+        };
+        println!(
+            "\r{name}: ✖ {ANSI_ITALIC}day {} failed: {error}{ANSI_RESET}",
+            record.day
+        );
+        return;
+    }
+
+    let duration_str = format_duration(&record.duration(), u128::from(record.samples));
+
+    if record.is_parse() {
+        println!("\rParse:{duration_str}");
+    } else {
+        print_result(
+            &record.answer,
+            &format!("Part {}", record.part),
+            &duration_str,
+        );
+    }
+
+    if let Some(stats) = &record.stats {
+        println!("  {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+}
+
+/// The year of a solution binary: the year in the name of `YYYY-NN` binaries,
+/// and `--year` or the main year for `NN` binaries.
+pub fn bin_year(bin_name: &str) -> Year {
+    match parse_bin_name(bin_name) {
+        Some((Some(year), _)) => year,
+        _ => get_year(),
+    }
+}
+
+/// The year passed as `--year` by `solve`. Defaults to the main year.
+fn get_year() -> Year {
+    let args: Vec<String> = env::args().collect();
+    let year = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1));
+
+    match year.map(|x| x.parse::<Year>()) {
+        Some(Ok(year)) => year,
+        Some(Err(e)) => {
+            eprintln!("Unexpected command-line input: {e}");
+            process::exit(1);
+        }
+        None => Year::main().unwrap_or_else(|| {
+            eprintln!("No year configured. Pass \"--year\" or set \"year\" in \"aoc.toml\".");
+            process::exit(1);
+        }),
+    }
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    stats: Option<BenchStats>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.
+///     Both are configured in the `benchmark` section of `aoc.toml`.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let measurement = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: base_time,
+            samples: 1,
+            stats: None,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Benchmarks a function. The first 10% of iterations warm up caches and are not measured.
+/// The reported duration is the median of the remaining samples.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
+    let settings = &config::get().benchmark;
+    let bench_iterations = (settings.target().as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples.into(), settings.max_samples.into());
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,19 +479,16 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    #[allow(clippy::cast_possible_truncation)]
+    let stats = BenchStats::new(&timers, warmup_iterations as u64);
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Measurement {
+        duration: stats
+            .as_ref()
+            .map_or(*base_time, |stats| stats.get(Statistic::Median)),
+        samples: bench_iterations,
+        stats,
+    }
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -129,12 +536,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the `--submit` part matches the current part.
+///  3. the answer was computed from the puzzle input.
+///  4. the answer is not known to be wrong from previous submissions, unless `--force` is passed.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::SubmissionOutcome, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +567,113 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !InputSource::from_args(&args).is_ok_and(|source| source.is_puzzle()) {
+        eprintln!("Refusing to submit: the answer was not computed from the puzzle input.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    let mut ledger = match Ledger::load(year, day) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if !args.contains(&"--force".into()) {
+        if let Err(rejection) = ledger.check(part, &answer) {
+            eprintln!("Refusing to submit: {rejection} Pass \"--force\" to submit anyway.");
+            process::exit(rejection.exit_code());
+        }
+    }
+
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(year, day, part, &answer);
+
+    if let Ok(outcome) = &outcome {
+        ledger.record(part, &answer, outcome);
+        if let Err(e) = ledger.save(year, day) {
+            eprintln!("failed to update answer ledger: {e}");
+        }
+    }
+
+    Some(outcome)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_year, solve_parse, solve_part, PartStatus};
+    use crate::template::{__bin_year, parse_bin_name};
+    use crate::{day, year};
+
+    #[test]
+    fn derives_year_from_bin_name() {
+        assert_eq!(parse_bin_name("03"), Some((None, day!(3))));
+        assert_eq!(
+            parse_bin_name("2025-03"),
+            Some((Some(year!(2025)), day!(3)))
+        );
+        assert_eq!(parse_bin_name("advent_of_code"), None);
+        assert_eq!(parse_bin_name("2025-3"), None);
+        assert_eq!(parse_bin_name("1999-03"), None);
+        assert_eq!(bin_year("2025-03"), year!(2025));
+        assert_eq!(__bin_year("2025-03"), 2025);
+    }
+
+    #[test]
+    fn reports_errors_and_panics() {
+        let record = solve_part(
+            |x: u32| Ok::<_, String>(x * 2),
+            21,
+            day!(1),
+            1,
+            false,
+            |_| {},
+        );
+        assert_eq!(record.status, PartStatus::Solved);
+        assert_eq!(record.answer.as_deref(), Some("42"));
+
+        let record = solve_part(
+            |_: u32| Err::<u32, _>("invalid input"),
+            21,
+            day!(1),
+            1,
+            false,
+            |_| {},
+        );
+        assert_eq!(record.status, PartStatus::Failed);
+        assert_eq!(record.error.as_deref(), Some("invalid input"));
+
+        let record = solve_part(
+            |_: u32| -> Option<u32> { panic!("out of bounds") },
+            21,
+            day!(1),
+            2,
+            false,
+            |_| {},
+        );
+        assert_eq!(record.status, PartStatus::Failed);
+        assert_eq!(record.error.as_deref(), Some("panicked: out of bounds"));
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn reports_parser_panics() {
+        let (parsed, record) = solve_parse(|x: u32| x * 2, 21, day!(1), false, |_| {});
+        assert_eq!(parsed, Some(42));
+        assert_eq!(record.status, PartStatus::Solved);
+
+        let (parsed, record) = solve_parse(
+            |_: u32| -> u32 { panic!("unexpected token") },
+            21,
+            day!(1),
+            false,
+            |_| {},
+        );
+        assert_eq!(parsed, None);
+        assert_eq!(record.status, PartStatus::Failed);
+        assert_eq!(record.error.as_deref(), Some("panicked: unexpected token"));
+    }
 }
@@ -0,0 +1,24 @@
+/// The trait-based alternative to the free `part_one` / `part_two` functions.
+use std::fmt::Display;
+
+use crate::template::runner::PartResult;
+
+/// A solution that parses its input once into a domain type shared by both parts.
+///
+/// Register an implementor with `advent_of_code::solution!(DAY => Type)`.
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Input;
+    /// The return type of part one: `Option<T>`, or `Result<T, E>` to report errors.
+    type Output1: PartResult;
+    /// The return type of part two: `Option<T>`, or `Result<T, E>` to report errors.
+    type Output2: PartResult;
+    /// The error returned when the input can not be parsed.
+    type Error: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+
+    fn part_one(input: &Self::Input) -> Self::Output1;
+
+    fn part_two(input: &Self::Input) -> Self::Output2;
+}
@@ -0,0 +1,180 @@
+/// Summary statistics over the samples of a benchmark run.
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// The statistic used to summarize a benchmark in the report and the README table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Statistic {
+    #[default]
+    Median,
+    Mean,
+    Min,
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "median" => Ok(Statistic::Median),
+            "mean" => Ok(Statistic::Mean),
+            "min" => Ok(Statistic::Min),
+            x => Err(format!(
+                "unknown statistic: {x}. Expecting \"median\", \"mean\" or \"min\"."
+            )),
+        }
+    }
+}
+
+/// Statistics over the samples of a benchmark, in nanoseconds.
+/// Warm-up iterations and outliers are not included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchStats {
+    pub mean_nanos: u64,
+    pub median_nanos: u64,
+    pub min_nanos: u64,
+    pub max_nanos: u64,
+    pub std_dev_nanos: u64,
+    /// Bounds of the 95% confidence interval of the mean.
+    pub ci_low_nanos: u64,
+    pub ci_high_nanos: u64,
+    pub warmup: u64,
+    pub outliers: u64,
+}
+
+impl BenchStats {
+    /// Computes statistics over `samples`, discarding outliers outside of the
+    /// 1.5 × IQR fences (Tukey's method).
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn new(samples: &[Duration], warmup: u64) -> Option<Self> {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        if nanos.is_empty() {
+            return None;
+        }
+
+        nanos.sort_by(f64::total_cmp);
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let total = nanos.len();
+        nanos.retain(|x| *x >= low && *x <= high);
+        let n = nanos.len() as f64;
+
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let std_dev = variance.sqrt();
+        let margin = 1.96 * std_dev / n.sqrt();
+
+        Some(Self {
+            mean_nanos: mean.round() as u64,
+            median_nanos: percentile(&nanos, 0.5).round() as u64,
+            min_nanos: nanos[0] as u64,
+            max_nanos: nanos[nanos.len() - 1] as u64,
+            std_dev_nanos: std_dev.round() as u64,
+            ci_low_nanos: (mean - margin).max(0.0).round() as u64,
+            ci_high_nanos: (mean + margin).round() as u64,
+            warmup,
+            outliers: (total - nanos.len()) as u64,
+        })
+    }
+
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        Duration::from_nanos(match statistic {
+            Statistic::Median => self.median_nanos,
+            Statistic::Mean => self.mean_nanos,
+            Statistic::Min => self.min_nanos,
+        })
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let d = Duration::from_nanos;
+        write!(
+            f,
+            "median {:.1?} · mean {:.1?} ± {:.1?} · min {:.1?} · max {:.1?} · 95% CI [{:.1?}, {:.1?}] · {} warm-up, {} outliers",
+            d(self.median_nanos),
+            d(self.mean_nanos),
+            d(self.std_dev_nanos),
+            d(self.min_nanos),
+            d(self.max_nanos),
+            d(self.ci_low_nanos),
+            d(self.ci_high_nanos),
+            self.warmup,
+            self.outliers
+        )
+    }
+}
+
+/// Linear interpolation between the closest ranks of a sorted slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, Statistic};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = BenchStats::new(&nanos(&[10, 20, 30, 40, 50]), 1).unwrap();
+        assert_eq!(stats.median_nanos, 30);
+        assert_eq!(stats.mean_nanos, 30);
+        assert_eq!(stats.min_nanos, 10);
+        assert_eq!(stats.max_nanos, 50);
+        assert_eq!(stats.std_dev_nanos, 16);
+        assert_eq!(stats.ci_low_nanos, 16);
+        assert_eq!(stats.ci_high_nanos, 44);
+        assert_eq!(stats.warmup, 1);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::new(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]), 0).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max_nanos, 102);
+        assert_eq!(stats.mean_nanos, 100);
+        assert_eq!(stats.get(Statistic::Median), Duration::from_nanos(100));
+        assert_eq!(stats.get(Statistic::Min), Duration::from_nanos(98));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::new(&nanos(&[42]), 0).unwrap();
+        assert_eq!(stats.median_nanos, 42);
+        assert_eq!(stats.std_dev_nanos, 0);
+        assert!(BenchStats::new(&[], 0).is_none());
+    }
+
+    #[test]
+    fn parses_statistic() {
+        assert_eq!("mean".parse::<Statistic>(), Ok(Statistic::Mean));
+        assert!("p99".parse::<Statistic>().is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::config;
+use crate::{AllDays, Day};
+
+/// The year of the first advent of code event.
+pub(crate) const FIRST_YEAR: u16 = 2015;
+
+/// The number of days of the events, starting from the year of each entry.
+const CALENDAR: &[(u16, u8)] = &[(FIRST_YEAR, 25), (2025, 12)];
+
+/// The number of days of the longest events, the largest valid [`Day`] of any year.
+pub(crate) const MAX_DAYS: u8 = max_days();
+
+const fn days_of_event(year: u16) -> u8 {
+    let mut days = CALENDAR[0].1;
+    let mut i = 0;
+    while i < CALENDAR.len() && CALENDAR[i].0 <= year {
+        days = CALENDAR[i].1;
+        i += 1;
+    }
+    days
+}
+
+const fn max_days() -> u8 {
+    let mut max = 0;
+    let mut i = 0;
+    while i < CALENDAR.len() {
+        if CALENDAR[i].1 > max {
+            max = CALENDAR[i].1;
+        }
+        i += 1;
+    }
+    max
+}
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2022).unwrap();
+/// assert_eq!(year.to_string(), "2022")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year with an event,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// The main year of the repository, configured by `year` in `aoc.toml` or `AOC_YEAR`.
+    pub fn main() -> Option<Self> {
+        config::get().year
+    }
+
+    /// Whether this is the main year of the repository.
+    /// Files of the main year live directly in `data` and `src/bin`.
+    pub fn is_main(self) -> bool {
+        Self::main() == Some(self)
+    }
+
+    /// The last day of the event of this year.
+    ///
+    /// ```
+    /// # use advent_of_code::{day, year};
+    /// assert_eq!(year!(2024).last_day(), day!(25));
+    /// assert_eq!(year!(2025).last_day(), day!(12));
+    /// ```
+    pub const fn last_day(self) -> Day {
+        Day::__new_unchecked(days_of_event(self.0))
+    }
+
+    /// An iterator that yields every day of the event of this year.
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.last_day())
+    }
+
+    /// Whether the event of this year has a puzzle on `day`.
+    pub fn has_day(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl TryFrom<u16> for Year {
+    type Error = YearFromStrError;
+
+    fn try_from(year: u16) -> Result<Self, Self::Error> {
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+impl From<Year> for u16 {
+    fn from(year: Year) -> Self {
+        year.0
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, year, Day};
+
+    #[test]
+    fn days_of_event() {
+        assert_eq!(year!(2015).days().count(), 25);
+        assert_eq!(year!(2024).days().last(), Some(day!(25)));
+        assert_eq!(year!(2025).days().last(), Some(day!(12)));
+        assert!(year!(2025).has_day(day!(12)));
+        assert!(!year!(2025).has_day(day!(13)));
+        assert_eq!(day!(2025, 12), day!(12));
+        assert_eq!(Day::new(25), Some(year!(2024).last_day()));
+        assert_eq!(Day::new(26), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,19 @@
+//! {{title}}
+{{imports}}advent_of_code::solution!({{day}}, parse);
+
+/// The input as rows of bytes, indexed by `grid[y][x]`.
+pub type Grid = Vec<Vec<u8>>;
+
+pub fn parse(input: &str) -> Grid {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+pub fn part_one(grid: &Grid) -> Option<{{type}}> {
+    None
+}
+
+pub fn part_two(grid: &Grid) -> Option<{{type}}> {
+    None
+}
+
+advent_of_code::example_tests!();
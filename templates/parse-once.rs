@@ -0,0 +1,16 @@
+//! {{title}}
+{{imports}}advent_of_code::solution!({{day}}, parse);
+
+pub fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+pub fn part_one(lines: &[&str]) -> Option<{{type}}> {
+    None
+}
+
+pub fn part_two(lines: &[&str]) -> Option<{{type}}> {
+    None
+}
+
+advent_of_code::example_tests!();